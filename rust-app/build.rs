@@ -1,3 +1,63 @@
+use std::path::Path;
+use std::process::Command;
+
 fn main() {
     println!("cargo:rerun-if-changed=script.ld");
+
+    // Identifies the build in GET_VERSION_STR. Builds without a git checkout, like Nix's, can set
+    // SUI_BUILD_ID instead.
+    println!("cargo:rerun-if-env-changed=SUI_BUILD_ID");
+    if std::env::var("SUI_BUILD_ID").is_err() {
+        watch_git();
+    }
+    let build_id = std::env::var("SUI_BUILD_ID")
+        .ok()
+        .or_else(git_revision)
+        .unwrap_or_else(|| "unknown".into());
+    println!("cargo:rustc-env=SUI_BUILD_ID={build_id}");
+}
+
+// The abbreviated commit, marked when the tree has uncommitted changes
+fn git_revision() -> Option<String> {
+    let revision = git(&["rev-parse", "--short=12", "HEAD"])?;
+    let clean = Command::new("git")
+        .args(["diff", "--quiet", "HEAD"])
+        .status()
+        .ok()?
+        .success();
+    Some(if clean { revision } else { revision + "-dirty" })
+}
+
+// Reruns the script when HEAD moves, by checkout or by a commit on the current branch, or when the
+// index changes. Only files that exist are watched, as a missing one would rerun it on every build.
+// Watching anything stops Cargo from rerunning it on source edits, so the package's own files are
+// watched too, to keep the -dirty marker current.
+fn watch_git() {
+    for path in ["src", "Cargo.toml", "build.rs"] {
+        println!("cargo:rerun-if-changed={path}");
+    }
+    let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) else {
+        return;
+    };
+    let git_dir = Path::new(&git_dir);
+    let mut watched = vec![
+        git_dir.join("HEAD"),
+        git_dir.join("logs/HEAD"),
+        git_dir.join("index"),
+        git_dir.join("packed-refs"),
+    ];
+    if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        watched.push(git_dir.join(head_ref));
+    }
+    for path in watched.iter().filter(|path| path.exists()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().to_string())
 }
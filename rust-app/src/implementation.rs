@@ -1,4 +1,5 @@
 use crate::interface::*;
+use crate::path::*;
 use crate::settings::*;
 use crate::utils::*;
 use alamgu_async_block::*;
@@ -7,53 +8,203 @@ use arrayvec::ArrayVec;
 use core::fmt::Write;
 use ledger_crypto_helpers::common::{try_option, Address, HexSlice};
 use ledger_crypto_helpers::eddsa::{ed25519_public_key_bytes, eddsa_sign, with_public_keys};
-use ledger_crypto_helpers::hasher::{Blake2b, Hasher, HexHash};
+use ledger_crypto_helpers::hasher::{Blake2b, Hasher, HexHash, SHA256};
+use ledger_device_sdk::ecc::{ECPrivateKey, Secp256k1, Secp256r1, SeedDerive};
 use ledger_device_sdk::io::{StatusWords, SyscallError};
 use ledger_log::trace;
 use ledger_parser_combinators::async_parser::*;
 use ledger_parser_combinators::bcs::async_parser::*;
 use ledger_parser_combinators::interp::*;
-use ledger_prompts_ui::{final_accept_prompt, ScrollerError};
+use ledger_prompts_ui::final_accept_prompt;
 
 use core::convert::TryFrom;
 use core::future::Future;
 
 type SuiAddressRaw = [u8; SUI_ADDRESS_LENGTH];
 
-pub struct SuiPubKeyAddress(ledger_device_sdk::ecc::ECPublicKey<65, 'E'>, SuiAddressRaw);
+// The public key of an account, and the address it hashes to along with its scheme's flag
+pub struct SuiPubKeyAddress(ArrayVec<u8, 33>, SuiAddressRaw);
+
+impl SuiPubKeyAddress {
+    fn new(scheme: SignatureScheme, key_bytes: &[u8]) -> Option<Self> {
+        let mut public_key = ArrayVec::<u8, 33>::new();
+        public_key.try_extend_from_slice(key_bytes).ok()?;
+        let mut hasher: Blake2b = Hasher::new();
+        hasher.update(&[scheme as u8]);
+        hasher.update(&public_key);
+        let hash: [u8; SUI_ADDRESS_LENGTH] = hasher.finalize();
+        Some(SuiPubKeyAddress(public_key, hash))
+    }
+
+    // 32 bytes for Ed25519, 33 byte compressed points for the ECDSA schemes
+    pub fn public_key(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn address(&self) -> &SuiAddressRaw {
+        &self.1
+    }
+}
 
 impl Address<SuiPubKeyAddress, ledger_device_sdk::ecc::ECPublicKey<65, 'E'>> for SuiPubKeyAddress {
     fn get_address(
         key: &ledger_device_sdk::ecc::ECPublicKey<65, 'E'>,
     ) -> Result<Self, SyscallError> {
-        let key_bytes = ed25519_public_key_bytes(key);
-        let mut tmp = ArrayVec::<u8, 33>::new();
-        let _ = tmp.try_push(0); // SIGNATURE_SCHEME_TO_FLAG['ED25519']
-        let _ = tmp.try_extend_from_slice(key_bytes);
-        let mut hasher: Blake2b = Hasher::new();
-        hasher.update(&tmp);
-        let hash: [u8; SUI_ADDRESS_LENGTH] = hasher.finalize();
-        Ok(SuiPubKeyAddress(key.clone(), hash))
+        SuiPubKeyAddress::new(SignatureScheme::Ed25519, ed25519_public_key_bytes(key))
+            .ok_or(SyscallError::Unspecified)
     }
     fn get_binary_address(&self) -> &[u8] {
         &self.1
     }
 }
 
+fn ecdsa_public_key(
+    scheme: SignatureScheme,
+    key: &ECPrivateKey<32, 'W'>,
+) -> Option<SuiPubKeyAddress> {
+    let public = key.public_key().ok()?;
+    // Compress the uncompressed 0x04 || x || y point
+    let mut compressed = [0; 33];
+    compressed[0] = 0x02 | (public.pubkey[64] & 1);
+    compressed[1..].copy_from_slice(&public.pubkey[1..33]);
+    SuiPubKeyAddress::new(scheme, &compressed)
+}
+
+// Derives the public key and address for a path, with the scheme its purpose selects
+pub fn with_public_key<V>(
+    path: &[u32],
+    mut f: impl FnMut(&SuiPubKeyAddress) -> Option<V>,
+) -> Option<V> {
+    match SignatureScheme::from_path(path)? {
+        SignatureScheme::Ed25519 => {
+            with_public_keys(path, true, |_, address: &SuiPubKeyAddress| {
+                try_option(f(address))
+            })
+            .ok()
+        }
+        scheme @ SignatureScheme::Secp256k1 => f(&ecdsa_public_key(
+            scheme,
+            &Secp256k1::derive_from_path(path),
+        )?),
+        scheme @ SignatureScheme::Secp256r1 => f(&ecdsa_public_key(
+            scheme,
+            &Secp256r1::derive_from_path(path),
+        )?),
+    }
+}
+
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+const SECP256R1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xbc, 0xe6, 0xfa, 0xad, 0xa7, 0x17, 0x9e, 0x84, 0xf3, 0xb9, 0xca, 0xc2, 0xfc, 0x63, 0x25, 0x51,
+];
+
+// Splits the value of a DER INTEGER off the front of the input, left padded to 32 bytes
+fn der_integer(der: &[u8]) -> Option<([u8; 32], &[u8])> {
+    match der {
+        [0x02, length, rest @ ..] if rest.len() >= *length as usize => {
+            let (value, rest) = rest.split_at(*length as usize);
+            let first = value.iter().position(|b| *b != 0).unwrap_or(value.len());
+            let value = &value[first..];
+            if value.len() > 32 {
+                return None;
+            }
+            let mut padded = [0; 32];
+            padded[32 - value.len()..].copy_from_slice(value);
+            Some((padded, rest))
+        }
+        _ => None,
+    }
+}
+
+fn sub_be(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut result = [0; 32];
+    let mut borrow = 0;
+    for i in (0..32).rev() {
+        let diff = a[i] as i16 - b[i] as i16 - borrow;
+        borrow = (diff < 0) as i16;
+        result[i] = diff.rem_euclid(256) as u8;
+    }
+    result
+}
+
+// Sui verifies ECDSA signatures over the SHA256 of the intent message digest, as a 64 byte r || s
+// with s in the lower half of the curve order
+fn ecdsa_sign(
+    key: &ECPrivateKey<32, 'W'>,
+    digest: &[u8; 32],
+    order: &[u8; 32],
+) -> Option<[u8; 64]> {
+    let mut hasher: SHA256 = Hasher::new();
+    hasher.update(digest);
+    let hash: [u8; 32] = hasher.finalize();
+    let (der, der_length, _) = key.deterministic_sign(&hash).ok()?;
+    let body = match der.get(..der_length as usize)? {
+        [0x30, _, body @ ..] => body,
+        _ => return None,
+    };
+    let (r, rest) = der_integer(body)?;
+    let (mut s, _) = der_integer(rest)?;
+    let negated_s = sub_be(order, &s);
+    if s > negated_s {
+        s = negated_s;
+    }
+    let mut signature = [0; 64];
+    signature[..32].copy_from_slice(&r);
+    signature[32..].copy_from_slice(&s);
+    Some(signature)
+}
+
+// Signs the Blake2b digest of an intent message with the scheme the path selects
+pub fn sign_digest(path: &[u32], digest: &[u8; 32]) -> Option<[u8; 64]> {
+    match SignatureScheme::from_path(path)? {
+        SignatureScheme::Ed25519 => Some(eddsa_sign(path, true, digest).ok()?.0),
+        SignatureScheme::Secp256k1 => {
+            ecdsa_sign(&Secp256k1::derive_from_path(path), digest, &SECP256K1_ORDER)
+        }
+        SignatureScheme::Secp256r1 => {
+            ecdsa_sign(&Secp256r1::derive_from_path(path), digest, &SECP256R1_ORDER)
+        }
+    }
+}
+
 impl core::fmt::Display for SuiPubKeyAddress {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "0x{}", HexSlice(&self.1))
     }
 }
 
+// Displays an address with leading zeros dropped, the way Sui shows well-known packages like 0x2
+pub struct ShortAddress<'a>(pub &'a SuiAddressRaw);
+
+impl core::fmt::Display for ShortAddress<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x")?;
+        let mut leading = true;
+        for b in self.0 {
+            for nibble in [b >> 4, b & 0xf] {
+                if leading && nibble == 0 {
+                    continue;
+                }
+                leading = false;
+                write!(f, "{nibble:x}")?;
+            }
+        }
+        if leading {
+            write!(f, "0")?;
+        }
+        Ok(())
+    }
+}
+
 pub type BipParserImplT =
     impl AsyncParser<Bip32Key, ByteStream> + HasOutput<Bip32Key, Output = ArrayVec<u32, 10>>;
 pub const BIP_PATH_PARSER: BipParserImplT = SubInterp(DefaultInterp);
 
-// Need a path of length 5, as make_bip32_path panics with smaller paths
-pub const BIP32_PREFIX: [u32; 5] =
-    ledger_device_sdk::ecc::make_bip32_path(b"m/44'/784'/123'/0'/0'");
-
 pub async fn get_address_apdu(io: HostIO, prompt: bool) {
     let input = match io.get_params::<1>() {
         Some(v) => v,
@@ -61,34 +212,21 @@ pub async fn get_address_apdu(io: HostIO, prompt: bool) {
     };
 
     let path = BIP_PATH_PARSER.parse(&mut input[0].clone()).await;
-
-    if !path.starts_with(&BIP32_PREFIX[0..2]) {
-        reject::<()>(SyscallError::InvalidParameter as u16).await;
-    }
+    confirm_path(&path).await;
 
     let mut rv = ArrayVec::<u8, 220>::new();
 
-    if with_public_keys(&path, true, |key, address: &SuiPubKeyAddress| {
-        try_option(|| -> Option<()> {
-            if prompt {
-                scroller("Provide Public Key", |_w| Ok(()))?;
-                scroller_paginated("Address", |w| Ok(write!(w, "{address}")?))?;
-                final_accept_prompt(&[])?;
-            }
-
-            let key_bytes = ed25519_public_key_bytes(key);
-
-            rv.try_push(u8::try_from(key_bytes.len()).ok()?).ok()?;
-            rv.try_extend_from_slice(key_bytes).ok()?;
+    if with_public_key(&path, |address| {
+        if prompt {
+            scroller("Provide Public Key", |_w| Ok(()))?;
+            scroller_paginated("Path", |w| Ok(write!(w, "{}", DerivationPath(&path))?))?;
+            scroller_paginated("Address", |w| Ok(write!(w, "{address}")?))?;
+            final_accept_prompt(&[])?;
+        }
 
-            // And we'll send the address along;
-            let binary_address = address.get_binary_address();
-            rv.try_push(u8::try_from(binary_address.len()).ok()?).ok()?;
-            rv.try_extend_from_slice(binary_address).ok()?;
-            Some(())
-        }())
+        push_public_key(&mut rv, address)
     })
-    .is_err()
+    .is_none()
     {
         reject::<()>(StatusWords::UserCancelled as u16).await;
     }
@@ -96,11 +234,75 @@ pub async fn get_address_apdu(io: HostIO, prompt: bool) {
     io.result_final(&rv).await;
 }
 
+// The length of the public key, the key, then the length of the address and the address
+fn push_public_key<const N: usize>(
+    rv: &mut ArrayVec<u8, N>,
+    address: &SuiPubKeyAddress,
+) -> Option<()> {
+    let key_bytes = address.public_key();
+
+    rv.try_push(u8::try_from(key_bytes.len()).ok()?).ok()?;
+    rv.try_extend_from_slice(key_bytes).ok()?;
+
+    // And we'll send the address along;
+    let binary_address = address.get_binary_address();
+    rv.try_push(u8::try_from(binary_address.len()).ok()?).ok()?;
+    rv.try_extend_from_slice(binary_address).ok()?;
+    Some(())
+}
+
+// Returns the public key and address of each path in the list, in order, as GET_PUBKEY does for
+// one. Each is sent as soon as it is derived, so that wallets can discover accounts in one request.
+pub async fn get_pubkeys_apdu(io: HostIO) {
+    let mut input = match io.get_params::<1>() {
+        Some(v) => v,
+        None => reject(SyscallError::InvalidParameter as u16).await,
+    };
+
+    let [count]: [u8; 1] = input[0].read().await;
+    for _ in 0..count {
+        let path = BIP_PATH_PARSER.parse(&mut input[0]).await;
+        confirm_path(&path).await;
+
+        let mut rv = ArrayVec::<u8, 68>::new();
+        if with_public_key(&path, |address| push_public_key(&mut rv, address)).is_none() {
+            reject::<()>(SyscallError::Unspecified as u16).await;
+        }
+        io.result_accumulating(&rv).await;
+    }
+
+    io.result_final(&[]).await;
+}
+
+pub const REJECTION_DETAIL_LENGTH: usize = 128;
+
+// Why the last transaction was not recognized, with the construct that was not, e.g.
+// "MoveCall 0x2::kiosk::list". The check parse runs inside TryFuture, which drops the status word,
+// so it is kept here to be returned, or shown, when the transaction is blind signed instead.
+static mut REJECTION: Option<(SuiStatusWords, ArrayString<REJECTION_DETAIL_LENGTH>)> = None;
+
+async fn reject_for<T>(
+    file: &'static str,
+    line: u32,
+    reason: SuiStatusWords,
+    detail: impl FnOnce(&mut ArrayString<REJECTION_DETAIL_LENGTH>) -> core::fmt::Result,
+) -> T {
+    let mut description = ArrayString::new();
+    // A detail too long to keep is cut short, which still says what it was about
+    let _ = detail(&mut description);
+    unsafe { REJECTION = Some((reason, description)) };
+    reject_on(file, line, reason as u16).await
+}
+
+pub const PURE_ARG_LENGTH: usize = 32;
+
 pub enum CallArg {
     RecipientAddress(SuiAddressRaw),
     Amount(u64),
-    OtherPure,
-    ObjectArg,
+    OtherPure(ArrayVec<u8, PURE_ARG_LENGTH>),
+    // A pure value too long to keep, by its length and Blake2b hash
+    LongPure(u32, [u8; 32]),
+    ObjectArg(SuiAddressRaw),
 }
 
 impl HasOutput<CallArgSchema> for DefaultInterp {
@@ -134,11 +336,23 @@ impl<BS: Clone + Readable> AsyncParser<CallArgSchema, BS> for DefaultInterp {
                             )
                             .await,
                         ),
+                        _ if length as usize > PURE_ARG_LENGTH => {
+                            let mut hasher: Blake2b = Hasher::new();
+                            for _ in 0..length {
+                                let b: [u8; 1] = input.read().await;
+                                hasher.update(&b);
+                            }
+                            CallArg::LongPure(length, hasher.finalize())
+                        }
                         _ => {
+                            // Keep short values around so they can be shown as Move call
+                            // arguments
+                            let mut bytes = ArrayVec::new();
                             for _ in 0..length {
-                                let _: [u8; 1] = input.read().await;
+                                let [b]: [u8; 1] = input.read().await;
+                                let _ = bytes.try_push(b);
                             }
-                            CallArg::OtherPure
+                            CallArg::OtherPure(bytes)
                         }
                     }
                 }
@@ -146,38 +360,50 @@ impl<BS: Clone + Readable> AsyncParser<CallArgSchema, BS> for DefaultInterp {
                     let enum_variant =
                         <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input)
                             .await;
-                    match enum_variant {
+                    let object_id = match enum_variant {
                         0 => {
                             trace!("CallArgSchema: ObjectArg: ImmOrOwnedObject");
-                            object_ref_parser().parse(input).await;
+                            let (object_id, _, _) =
+                                <(DefaultInterp, DefaultInterp, DefaultInterp) as AsyncParser<
+                                    ObjectRef,
+                                    BS,
+                                >>::parse(
+                                    &(DefaultInterp, DefaultInterp, DefaultInterp), input
+                                )
+                                .await;
+                            object_id
                         }
                         1 => {
                             trace!("CallArgSchema: ObjectArg: SharedObject");
-                            <(DefaultInterp, DefaultInterp, DefaultInterp) as AsyncParser<
-                                SharedObject,
-                                BS,
-                            >>::parse(
-                                &(DefaultInterp, DefaultInterp, DefaultInterp), input
-                            )
-                            .await;
+                            let (object_id, _, _) =
+                                <(DefaultInterp, DefaultInterp, DefaultInterp) as AsyncParser<
+                                    SharedObject,
+                                    BS,
+                                >>::parse(
+                                    &(DefaultInterp, DefaultInterp, DefaultInterp), input
+                                )
+                                .await;
+                            object_id
                         }
                         _ => {
-                            reject_on(
+                            reject_for(
                                 core::file!(),
                                 core::line!(),
-                                SyscallError::NotSupported as u16,
+                                SuiStatusWords::UnsupportedInput,
+                                |w| write!(w, "Object input kind {enum_variant}"),
                             )
                             .await
                         }
-                    }
-                    CallArg::ObjectArg
+                    };
+                    CallArg::ObjectArg(object_id)
                 }
                 _ => {
                     trace!("CallArgSchema: Unknown enum: {}", enum_variant);
-                    reject_on(
+                    reject_for(
                         core::file!(),
                         core::line!(),
-                        SyscallError::NotSupported as u16,
+                        SuiStatusWords::UnsupportedInput,
+                        |w| write!(w, "Input kind {enum_variant}"),
                     )
                     .await
                 }
@@ -186,18 +412,366 @@ impl<BS: Clone + Readable> AsyncParser<CallArgSchema, BS> for DefaultInterp {
     }
 }
 
-pub const TRANSFER_OBJECT_ARRAY_LENGTH: usize = 1;
-pub const SPLIT_COIN_ARRAY_LENGTH: usize = 8;
+pub const TRANSFER_OBJECT_ARRAY_LENGTH: usize = 8;
+// Each split coin is kept with its recipient until the end of the transaction, at 64 bytes: 256
+// bytes, against 512 for 8. Paying 4 recipients takes all 8 inputs.
+pub const SPLIT_COIN_ARRAY_LENGTH: usize = 4;
+pub const MERGE_COINS_ARRAY_LENGTH: usize = 16;
+pub const MAKE_MOVE_VEC_ARRAY_LENGTH: usize = 16;
+// A MoveCall is the largest Command, held while the next command is parsed: 272 bytes with these
+// limits (6 bytes an argument), against 384 with 16 arguments and 160 characters of type arguments
+pub const MOVE_CALL_ARGUMENTS_LENGTH: usize = 8;
+// The module hashes and dependency ids are all kept to be sorted, at 32 bytes each: 256 bytes,
+// against 512 for 16. A package of one module depending on the framework takes 3.
+pub const PACKAGE_COMPONENTS_LENGTH: usize = 8;
+pub const IDENTIFIER_LENGTH: usize = 40;
+// Room for one type from a package outside the framework, whose address is shown in full
+pub const TYPE_ARGUMENTS_LENGTH: usize = 96;
+pub const TYPE_TAG_DEPTH: usize = 8;
 
 pub enum Command {
+    MoveCall(MoveCall),
     TransferObject(ArrayVec<Argument, TRANSFER_OBJECT_ARRAY_LENGTH>, Argument),
     SplitCoins(Argument, ArrayVec<Argument, SPLIT_COIN_ARRAY_LENGTH>),
+    MergeCoins(Argument, ArrayVec<Argument, MERGE_COINS_ARRAY_LENGTH>),
+    Publish(Package),
+    MakeMoveVec(
+        Option<ArrayString<TYPE_ARGUMENTS_LENGTH>>,
+        ArrayVec<Argument, MAKE_MOVE_VEC_ARRAY_LENGTH>,
+    ),
+    Upgrade(Package, SuiAddressRaw, Argument),
+}
+
+pub struct Package {
+    pub modules: u32,
+    pub digest: [u8; 32],
+}
+
+pub struct MoveCall {
+    pub package: SuiAddressRaw,
+    pub module: ArrayString<IDENTIFIER_LENGTH>,
+    pub function: ArrayString<IDENTIFIER_LENGTH>,
+    // Rendered as Move source, e.g. "0x2::sui::SUI, u64", as that is all we ever do with them
+    pub type_arguments: ArrayString<TYPE_ARGUMENTS_LENGTH>,
+    pub arguments: ArrayVec<Argument, MOVE_CALL_ARGUMENTS_LENGTH>,
+}
+
+impl HasOutput<IdentifierSchema> for DefaultInterp {
+    type Output = ArrayString<IDENTIFIER_LENGTH>;
+}
+
+impl<BS: Clone + Readable> AsyncParser<IdentifierSchema, BS> for DefaultInterp {
+    type State<'c> = impl Future<Output = Self::Output> + 'c where BS: 'c;
+    fn parse<'a: 'c, 'b: 'c, 'c>(&'b self, input: &'a mut BS) -> Self::State<'c> {
+        async move {
+            let length =
+                <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
+            let mut identifier = ArrayString::new();
+            for _ in 0..length {
+                let [c]: [u8; 1] = input.read().await;
+                // Move identifiers are restricted to ASCII
+                if !c.is_ascii() {
+                    reject_for::<()>(
+                        core::file!(),
+                        core::line!(),
+                        SuiStatusWords::UnsupportedType,
+                        |w| w.write_str("Non-ASCII identifier"),
+                    )
+                    .await;
+                }
+                if identifier.try_push(char::from(c)).is_err() {
+                    reject_for::<()>(
+                        core::file!(),
+                        core::line!(),
+                        SuiStatusWords::TooLarge,
+                        |w| write!(w, "Identifier over {IDENTIFIER_LENGTH} characters"),
+                    )
+                    .await;
+                }
+            }
+            identifier
+        }
+    }
+}
+
+// Parses one TypeTag and appends it to out, in Move syntax.
+// Type tags nest through vectors and struct type parameters; instead of recursing we keep a
+// stack of how many type tags are still expected at each level of nesting.
+async fn parse_type_tag<BS: Clone + Readable, const N: usize>(
+    input: &mut BS,
+    out: &mut ArrayString<N>,
+) {
+    let mut pending: ArrayVec<u32, TYPE_TAG_DEPTH> = ArrayVec::new();
+    let _ = pending.try_push(1);
+    let mut ok = true;
+    loop {
+        let enum_variant =
+            <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
+        let nested = match enum_variant {
+            0 => {
+                ok &= out.write_str("bool").is_ok();
+                0
+            }
+            1 => {
+                ok &= out.write_str("u8").is_ok();
+                0
+            }
+            2 => {
+                ok &= out.write_str("u64").is_ok();
+                0
+            }
+            3 => {
+                ok &= out.write_str("u128").is_ok();
+                0
+            }
+            4 => {
+                ok &= out.write_str("address").is_ok();
+                0
+            }
+            5 => {
+                ok &= out.write_str("signer").is_ok();
+                0
+            }
+            6 => {
+                ok &= out.write_str("vector<").is_ok();
+                1
+            }
+            7 => {
+                let address = <DefaultInterp as AsyncParser<AccountAddress, BS>>::parse(
+                    &DefaultInterp,
+                    input,
+                )
+                .await;
+                let module = <DefaultInterp as AsyncParser<IdentifierSchema, BS>>::parse(
+                    &DefaultInterp,
+                    input,
+                )
+                .await;
+                let name = <DefaultInterp as AsyncParser<IdentifierSchema, BS>>::parse(
+                    &DefaultInterp,
+                    input,
+                )
+                .await;
+                let type_params =
+                    <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
+                ok &= write!(out, "{}::{}::{}", ShortAddress(&address), module, name).is_ok();
+                if type_params > 0 {
+                    ok &= out.write_char('<').is_ok();
+                }
+                type_params
+            }
+            8 => {
+                ok &= out.write_str("u16").is_ok();
+                0
+            }
+            9 => {
+                ok &= out.write_str("u32").is_ok();
+                0
+            }
+            10 => {
+                ok &= out.write_str("u256").is_ok();
+                0
+            }
+            _ => {
+                trace!("TypeTag: Unknown enum: {}", enum_variant);
+                reject_for(
+                    core::file!(),
+                    core::line!(),
+                    SuiStatusWords::UnsupportedType,
+                    |w| write!(w, "Type tag kind {enum_variant}"),
+                )
+                .await
+            }
+        };
+        if nested > 0 {
+            if pending.try_push(nested).is_err() {
+                reject_for::<()>(
+                    core::file!(),
+                    core::line!(),
+                    SuiStatusWords::TooLarge,
+                    |w| write!(w, "Type nested over {TYPE_TAG_DEPTH} deep"),
+                )
+                .await;
+            }
+            continue;
+        }
+        // The tag just read is complete, as may be the levels enclosing it
+        while let Some(remaining) = pending.last_mut() {
+            *remaining -= 1;
+            if *remaining > 0 {
+                ok &= out.write_str(", ").is_ok();
+                break;
+            }
+            pending.pop();
+            if !pending.is_empty() {
+                ok &= out.write_char('>').is_ok();
+            }
+        }
+        if pending.is_empty() {
+            break;
+        }
+    }
+    if !ok {
+        reject_for::<()>(
+            core::file!(),
+            core::line!(),
+            SuiStatusWords::TooLarge,
+            |w| write!(w, "Type over {N} characters"),
+        )
+        .await;
+    }
+}
+
+impl HasOutput<MoveCallSchema> for DefaultInterp {
+    type Output = MoveCall;
+}
+
+impl<BS: Clone + Readable> AsyncParser<MoveCallSchema, BS> for DefaultInterp {
+    type State<'c> = impl Future<Output = Self::Output> + 'c where BS: 'c;
+    fn parse<'a: 'c, 'b: 'c, 'c>(&'b self, input: &'a mut BS) -> Self::State<'c> {
+        async move {
+            let package =
+                <DefaultInterp as AsyncParser<ObjectID, BS>>::parse(&DefaultInterp, input).await;
+            let module =
+                <DefaultInterp as AsyncParser<IdentifierSchema, BS>>::parse(&DefaultInterp, input)
+                    .await;
+            let function =
+                <DefaultInterp as AsyncParser<IdentifierSchema, BS>>::parse(&DefaultInterp, input)
+                    .await;
+            trace!("MoveCallSchema: {}::{}", module.as_str(), function.as_str());
+            let mut type_arguments = ArrayString::new();
+            let length =
+                <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
+            for i in 0..length {
+                if i > 0 && type_arguments.try_push_str(", ").is_err() {
+                    reject_for::<()>(
+                        core::file!(),
+                        core::line!(),
+                        SuiStatusWords::TooLarge,
+                        |w| write!(w, "Type arguments over {TYPE_ARGUMENTS_LENGTH} characters"),
+                    )
+                    .await;
+                }
+                parse_type_tag(input, &mut type_arguments).await;
+            }
+            let arguments = parse_arguments(input, "MoveCall").await;
+            MoveCall {
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments,
+            }
+        }
+    }
+}
+
+impl HasOutput<PackageSchema> for DefaultInterp {
+    type Output = Package;
+}
+
+impl<BS: Clone + Readable> AsyncParser<PackageSchema, BS> for DefaultInterp {
+    type State<'c> = impl Future<Output = Self::Output> + 'c where BS: 'c;
+    fn parse<'a: 'c, 'b: 'c, 'c>(&'b self, input: &'a mut BS) -> Self::State<'c> {
+        async move {
+            // The package digest is computed like MovePackage::compute_digest_for_modules_and_deps
+            // does it: the module hashes and dependency ids are sorted and hashed together.
+            let mut components: ArrayVec<[u8; 32], PACKAGE_COMPONENTS_LENGTH> = ArrayVec::new();
+            let modules =
+                <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
+            trace!("PackageSchema: Modules: {}", modules);
+            for _ in 0..modules {
+                let length =
+                    <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
+                let mut hasher: Blake2b = Hasher::new();
+                // Read alongside the module hashes, so smaller than hash_stream's
+                const CHUNK_SIZE: usize = 64;
+                let (chunks, rem) = (length as usize / CHUNK_SIZE, length as usize % CHUNK_SIZE);
+                for _ in 0..chunks {
+                    let b: [u8; CHUNK_SIZE] = input.read().await;
+                    hasher.update(&b);
+                }
+                for _ in 0..rem {
+                    let b: [u8; 1] = input.read().await;
+                    hasher.update(&b);
+                }
+                if components.try_push(hasher.finalize()).is_err() {
+                    reject_for::<()>(
+                        core::file!(),
+                        core::line!(),
+                        SuiStatusWords::TooLarge,
+                        |w| {
+                            write!(
+                                w,
+                                "Package over {PACKAGE_COMPONENTS_LENGTH} modules and dependencies"
+                            )
+                        },
+                    )
+                    .await;
+                }
+            }
+            let dependencies =
+                <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
+            trace!("PackageSchema: Dependencies: {}", dependencies);
+            for _ in 0..dependencies {
+                let id = <DefaultInterp as AsyncParser<ObjectID, BS>>::parse(&DefaultInterp, input)
+                    .await;
+                if components.try_push(id).is_err() {
+                    reject_for::<()>(
+                        core::file!(),
+                        core::line!(),
+                        SuiStatusWords::TooLarge,
+                        |w| {
+                            write!(
+                                w,
+                                "Package over {PACKAGE_COMPONENTS_LENGTH} modules and dependencies"
+                            )
+                        },
+                    )
+                    .await;
+                }
+            }
+            components.sort_unstable();
+            let mut hasher: Blake2b = Hasher::new();
+            for c in &components {
+                hasher.update(c);
+            }
+            Package {
+                modules,
+                digest: hasher.finalize(),
+            }
+        }
+    }
 }
 
 impl HasOutput<CommandSchema> for DefaultInterp {
     type Output = Command;
 }
 
+// The combinator's own Vec would reject an overlong list without saying why
+async fn parse_arguments<BS: Clone + Readable, const N: usize>(
+    input: &mut BS,
+    command: &'static str,
+) -> ArrayVec<Argument, N> {
+    let length = <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
+    if length as usize > N {
+        reject_for::<()>(
+            core::file!(),
+            core::line!(),
+            SuiStatusWords::TooLarge,
+            |w| write!(w, "{command} with over {N} arguments"),
+        )
+        .await;
+    }
+    let mut arguments = ArrayVec::new();
+    for _ in 0..length {
+        arguments.push(
+            <DefaultInterp as AsyncParser<ArgumentSchema, BS>>::parse(&DefaultInterp, input).await,
+        );
+    }
+    arguments
+}
+
 impl<BS: Clone + Readable> AsyncParser<CommandSchema, BS> for DefaultInterp {
     type State<'c> = impl Future<Output = Self::Output> + 'c where BS: 'c;
     fn parse<'a: 'c, 'b: 'c, 'c>(&'b self, input: &'a mut BS) -> Self::State<'c> {
@@ -205,13 +779,19 @@ impl<BS: Clone + Readable> AsyncParser<CommandSchema, BS> for DefaultInterp {
             let enum_variant =
                 <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
             match enum_variant {
+                0 => {
+                    trace!("CommandSchema: MoveCall");
+                    Command::MoveCall(
+                        <DefaultInterp as AsyncParser<MoveCallSchema, BS>>::parse(
+                            &DefaultInterp,
+                            input,
+                        )
+                        .await,
+                    )
+                }
                 1 => {
                     trace!("CommandSchema: TransferObject");
-                    let v1 = <SubInterp<DefaultInterp> as AsyncParser<
-                        Vec<ArgumentSchema, TRANSFER_OBJECT_ARRAY_LENGTH>,
-                        BS,
-                    >>::parse(&SubInterp(DefaultInterp), input)
-                    .await;
+                    let v1 = parse_arguments(input, "TransferObjects").await;
                     let v2 = <DefaultInterp as AsyncParser<ArgumentSchema, BS>>::parse(
                         &DefaultInterp,
                         input,
@@ -226,19 +806,76 @@ impl<BS: Clone + Readable> AsyncParser<CommandSchema, BS> for DefaultInterp {
                         input,
                     )
                     .await;
-                    let v2 = <SubInterp<DefaultInterp> as AsyncParser<
-                        Vec<ArgumentSchema, SPLIT_COIN_ARRAY_LENGTH>,
-                        BS,
-                    >>::parse(&SubInterp(DefaultInterp), input)
-                    .await;
+                    let v2 = parse_arguments(input, "SplitCoins").await;
                     Command::SplitCoins(v1, v2)
                 }
+                3 => {
+                    trace!("CommandSchema: MergeCoins");
+                    let v1 = <DefaultInterp as AsyncParser<ArgumentSchema, BS>>::parse(
+                        &DefaultInterp,
+                        input,
+                    )
+                    .await;
+                    let v2 = parse_arguments(input, "MergeCoins").await;
+                    Command::MergeCoins(v1, v2)
+                }
+                4 => {
+                    trace!("CommandSchema: Publish");
+                    Command::Publish(
+                        <DefaultInterp as AsyncParser<PackageSchema, BS>>::parse(
+                            &DefaultInterp,
+                            input,
+                        )
+                        .await,
+                    )
+                }
+                5 => {
+                    trace!("CommandSchema: MakeMoveVec");
+                    let [has_type]: [u8; 1] = input.read().await;
+                    let type_tag = match has_type {
+                        0 => None,
+                        1 => {
+                            let mut type_tag = ArrayString::new();
+                            parse_type_tag(input, &mut type_tag).await;
+                            Some(type_tag)
+                        }
+                        tag => {
+                            reject_for(
+                                core::file!(),
+                                core::line!(),
+                                SuiStatusWords::UnsupportedType,
+                                |w| write!(w, "MakeMoveVec type option tag {tag}"),
+                            )
+                            .await
+                        }
+                    };
+                    let v2 = parse_arguments(input, "MakeMoveVec").await;
+                    Command::MakeMoveVec(type_tag, v2)
+                }
+                6 => {
+                    trace!("CommandSchema: Upgrade");
+                    let package = <DefaultInterp as AsyncParser<PackageSchema, BS>>::parse(
+                        &DefaultInterp,
+                        input,
+                    )
+                    .await;
+                    let package_id =
+                        <DefaultInterp as AsyncParser<ObjectID, BS>>::parse(&DefaultInterp, input)
+                            .await;
+                    let ticket = <DefaultInterp as AsyncParser<ArgumentSchema, BS>>::parse(
+                        &DefaultInterp,
+                        input,
+                    )
+                    .await;
+                    Command::Upgrade(package, package_id, ticket)
+                }
                 _ => {
                     trace!("CommandSchema: Unknown enum: {}", enum_variant);
-                    reject_on(
+                    reject_for(
                         core::file!(),
                         core::line!(),
-                        SyscallError::NotSupported as u16,
+                        SuiStatusWords::UnsupportedCommand,
+                        |w| write!(w, "Command kind {enum_variant}"),
                     )
                     .await
                 }
@@ -293,10 +930,11 @@ impl<BS: Clone + Readable> AsyncParser<ArgumentSchema, BS> for DefaultInterp {
                     )
                 }
                 _ => {
-                    reject_on(
+                    reject_for(
                         core::file!(),
                         core::line!(),
-                        SyscallError::NotSupported as u16,
+                        SuiStatusWords::UnsupportedArgument,
+                        |w| write!(w, "Argument kind {enum_variant}"),
                     )
                     .await
                 }
@@ -305,177 +943,724 @@ impl<BS: Clone + Readable> AsyncParser<ArgumentSchema, BS> for DefaultInterp {
     }
 }
 
-impl<const PROMPT: bool> HasOutput<ProgrammableTransaction<PROMPT>>
-    for ProgrammableTransaction<PROMPT>
-{
-    type Output = ();
+// Every input is kept until the commands that refer to it are parsed, at 40 bytes each: 320 bytes,
+// against 640 for 16
+pub const INPUT_ARRAY_LENGTH: usize = 8;
+pub const UPGRADE_ARRAY_LENGTH: usize = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TxType {
+    // Only moves SUI from the gas coin to a recipient
+    SuiTransfer,
+    // Stakes SUI split off the gas coin with a validator
+    Stake,
+    // Withdraws a StakedSui object
+    WithdrawStake,
+    // Anything else we can parse, each command is reviewed on its own
+    Programmable,
 }
 
-impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<ProgrammableTransaction<PROMPT>, BS>
-    for ProgrammableTransaction<PROMPT>
-{
-    type State<'c> = impl Future<Output = Self::Output> + 'c where BS: 'c;
-    fn parse<'a: 'c, 'b: 'c, 'c>(&'b self, input: &'a mut BS) -> Self::State<'c> {
-        async move {
-            let mut recipient = None;
-            let mut recipient_index = None;
-            let mut amounts: ArrayVec<(u64, u32), SPLIT_COIN_ARRAY_LENGTH> = ArrayVec::new();
+const SUI_FRAMEWORK_ADDRESS: SuiAddressRaw = {
+    let mut address = [0; SUI_ADDRESS_LENGTH];
+    address[SUI_ADDRESS_LENGTH - 1] = 2;
+    address
+};
 
-            // Handle inputs
-            {
-                let length =
+const SUI_SYSTEM_ADDRESS: SuiAddressRaw = {
+    let mut address = [0; SUI_ADDRESS_LENGTH];
+    address[SUI_ADDRESS_LENGTH - 1] = 3;
+    address
+};
+
+const SUI_SYSTEM_STATE_OBJECT_ID: SuiAddressRaw = {
+    let mut address = [0; SUI_ADDRESS_LENGTH];
+    address[SUI_ADDRESS_LENGTH - 1] = 5;
+    address
+};
+
+fn is_call_to(call: &MoveCall, package: &SuiAddressRaw, module: &str, function: &str) -> bool {
+    call.package == *package && call.module.as_str() == module && call.function.as_str() == function
+}
+
+fn is_system_state(arg: &Argument, inputs: &[CallArg]) -> bool {
+    match arg {
+        Argument::Input(i) => matches!(
+            inputs.get(*i as usize),
+            Some(CallArg::ObjectArg(id)) if *id == SUI_SYSTEM_STATE_OBJECT_ID
+        ),
+        _ => false,
+    }
+}
+
+// The validator of a request_add_stake call staking a coin split off the gas coin
+fn add_stake_validator(
+    call: &MoveCall,
+    inputs: &[CallArg],
+    split_coins: &[SplitCoin],
+) -> Option<SuiAddressRaw> {
+    if !is_call_to(call, &SUI_SYSTEM_ADDRESS, "sui_system", "request_add_stake") {
+        return None;
+    }
+    match call.arguments.as_slice() {
+        [state, coin, Argument::Input(validator)]
+            if is_system_state(state, inputs) && is_split_coin(coin, split_coins) =>
+        {
+            match inputs.get(*validator as usize) {
+                Some(CallArg::RecipientAddress(addr)) => Some(*addr),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// The StakedSui object of a request_withdraw_stake call
+fn withdraw_stake_object(call: &MoveCall, inputs: &[CallArg]) -> Option<SuiAddressRaw> {
+    if !is_call_to(
+        call,
+        &SUI_SYSTEM_ADDRESS,
+        "sui_system",
+        "request_withdraw_stake",
+    ) {
+        return None;
+    }
+    match call.arguments.as_slice() {
+        [state, Argument::Input(staked_sui)] if is_system_state(state, inputs) => {
+            match inputs.get(*staked_sui as usize) {
+                Some(CallArg::ObjectArg(id)) => Some(*id),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// A coin split off the gas coin, followed through the commands so we know where the SUI goes
+struct SplitCoin {
+    command: u16,
+    index: u16,
+    amount: u64,
+    used: bool,
+}
+
+#[derive(Clone, Copy)]
+enum TransferAmount {
+    Exact(u64),
+    // The whole gas coin is sent, whatever is left of it once gas is paid
+    EntireBalance,
+}
+
+#[inline(never)]
+fn show_recipient(
+    number: Option<usize>,
+    recipient: &SuiAddressRaw,
+    amount: TransferAmount,
+    sender_owns_gas: bool,
+) -> Option<()> {
+    let mut to_title: ArrayString<24> = ArrayString::new();
+    let mut amount_title: ArrayString<24> = ArrayString::new();
+    match number {
+        Some(n) => {
+            write!(to_title, "To {n}").ok()?;
+            write!(amount_title, "Amount {n}").ok()?;
+        }
+        None => {
+            write!(to_title, "To").ok()?;
+            write!(amount_title, "Amount").ok()?;
+        }
+    }
+    scroller_paginated(&to_title, |w| Ok(write!(w, "0x{}", HexSlice(recipient))?))?;
+    match amount {
+        TransferAmount::Exact(amount) => {
+            let (quotient, remainder_str) = get_amount_in_decimals(amount);
+            scroller_paginated(&amount_title, |w| {
+                Ok(write!(w, "SUI {quotient}.{}", remainder_str.as_str())?)
+            })
+        }
+        // A sponsor's gas coin is not the sender's balance
+        TransferAmount::EntireBalance if sender_owns_gas => {
+            scroller_paginated(&amount_title, |w| {
+                Ok(write!(w, "Entire balance (minus gas)")?)
+            })
+        }
+        TransferAmount::EntireBalance => scroller_paginated(&amount_title, |w| {
+            Ok(write!(w, "Gas owner's coin (minus gas)")?)
+        }),
+    }
+}
+
+// The command whose result an argument refers to
+fn result_command(arg: &Argument) -> Option<u16> {
+    match arg {
+        Argument::Result(i) | Argument::NestedResult(i, _) => Some(*i),
+        _ => None,
+    }
+}
+
+// In a sponsored transaction the gas coin is the sponsor's, so any use of it spends their SUI
+fn uses_gas_coin(command: &Command) -> bool {
+    let is_gas_coin = |arg: &Argument| matches!(arg, Argument::GasCoin);
+    match command {
+        Command::MoveCall(call) => call.arguments.iter().any(is_gas_coin),
+        Command::TransferObject(objects, recipient) => {
+            objects.iter().any(is_gas_coin) || is_gas_coin(recipient)
+        }
+        Command::SplitCoins(coin, amounts) => is_gas_coin(coin) || amounts.iter().any(is_gas_coin),
+        Command::MergeCoins(coin, coins) => is_gas_coin(coin) || coins.iter().any(is_gas_coin),
+        Command::Publish(_) => false,
+        Command::MakeMoveVec(_, elements) => elements.iter().any(is_gas_coin),
+        Command::Upgrade(_, _, ticket) => is_gas_coin(ticket),
+    }
+}
+
+fn is_object_input(arg: &Argument, inputs: &[CallArg]) -> bool {
+    match arg {
+        Argument::Input(i) => matches!(inputs.get(*i as usize), Some(CallArg::ObjectArg(_))),
+        _ => false,
+    }
+}
+
+fn is_split_coin(arg: &Argument, split_coins: &[SplitCoin]) -> bool {
+    match result_command(arg) {
+        Some(cmd) => split_coins.iter().any(|c| c.command == cmd),
+        None => false,
+    }
+}
+
+fn split_coin_index(arg: &Argument, split_coins: &[SplitCoin]) -> Option<usize> {
+    match arg {
+        Argument::NestedResult(cmd, ix) => split_coins
+            .iter()
+            .position(|c| c.command == *cmd && c.index == *ix),
+        // A plain Result can only refer to a command with a single result
+        Argument::Result(cmd) => {
+            let mut coins = split_coins
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.command == *cmd);
+            match (coins.next(), coins.next()) {
+                (Some((i, _)), None) => Some(i),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+// Checks the arguments of a command, and marks the split coins it uses. Returns the amount of SUI
+// in those coins.
+//
+// A split coin can only be used once: after being passed to a Move call, or merged into, it may
+// no longer hold the amount it was split off with.
+fn use_arguments(
+    arguments: &[Argument],
+    inputs: &[CallArg],
+    split_coins: &mut [SplitCoin],
+) -> Option<u64> {
+    let mut amount: u64 = 0;
+    for arg in arguments {
+        match arg {
+            Argument::Input(i) if (*i as usize) >= inputs.len() => return None,
+            _ if is_split_coin(arg, split_coins) => {
+                let index = split_coin_index(arg, split_coins)?;
+                let coin = &mut split_coins[index];
+                if coin.used {
+                    return None;
+                }
+                coin.used = true;
+                amount = amount.checked_add(coin.amount)?;
+            }
+            _ => {}
+        }
+    }
+    Some(amount)
+}
+
+fn write_argument<W: Write>(
+    w: &mut W,
+    arg: &Argument,
+    inputs: &[CallArg],
+    split_coins: &[SplitCoin],
+) -> core::fmt::Result {
+    match arg {
+        Argument::GasCoin => write!(w, "Gas coin"),
+        Argument::Input(i) => match inputs.get(*i as usize) {
+            // The parameter types of the function are not known here, so a pure value is shown
+            // as its BCS bytes, even where its length suggests a u64 or an address
+            Some(CallArg::Amount(amt)) => write!(w, "Pure 0x{}", HexSlice(&amt.to_le_bytes())),
+            Some(CallArg::RecipientAddress(addr)) => write!(w, "Pure 0x{}", HexSlice(addr)),
+            Some(CallArg::OtherPure(bytes)) => write!(w, "Pure 0x{}", HexSlice(bytes)),
+            Some(CallArg::LongPure(length, hash)) => {
+                write!(w, "Pure ({length} bytes) hash 0x{}", HexSlice(hash))
+            }
+            Some(CallArg::ObjectArg(id)) => write!(w, "Object 0x{}", HexSlice(id)),
+            None => Err(core::fmt::Error),
+        },
+        Argument::Result(i) | Argument::NestedResult(i, _) => {
+            match arg {
+                Argument::NestedResult(_, j) => write!(w, "Result {i}.{j}")?,
+                _ => write!(w, "Result {i}")?,
+            }
+            match split_coin_index(arg, split_coins) {
+                Some(coin) => {
+                    let (quotient, remainder_str) =
+                        get_amount_in_decimals(split_coins[coin].amount);
+                    write!(w, " (SUI {quotient}.{})", remainder_str.as_str())
+                }
+                None => Ok(()),
+            }
+        }
+    }
+}
+
+fn write_upgrade_policy<W: Write>(w: &mut W, policy: u8) -> core::fmt::Result {
+    // C.F. sui::package
+    match policy {
+        0 => write!(w, "Compatible"),
+        128 => write!(w, "Additive"),
+        192 => write!(w, "Dependency only"),
+        _ => write!(w, "Unknown ({policy})"),
+    }
+}
+
+#[inline(never)]
+fn show_arguments(
+    title: &str,
+    arguments: &[Argument],
+    inputs: &[CallArg],
+    split_coins: &[SplitCoin],
+) -> Option<()> {
+    for (i, arg) in arguments.iter().enumerate() {
+        let mut numbered_title: ArrayString<24> = ArrayString::new();
+        write!(numbered_title, "{} {}", title, i + 1).ok()?;
+        scroller_paginated(&numbered_title, |w| {
+            Ok(write_argument(w, arg, inputs, split_coins)?)
+        })?;
+    }
+    Some(())
+}
+
+#[inline(never)]
+fn show_move_call(call: &MoveCall, inputs: &[CallArg], split_coins: &[SplitCoin]) -> Option<()> {
+    scroller_paginated("Move Call", |w| {
+        Ok(write!(
+            w,
+            "{}::{}::{}",
+            ShortAddress(&call.package),
+            call.module,
+            call.function
+        )?)
+    })?;
+    if !call.type_arguments.is_empty() {
+        scroller_paginated("Type Arguments", |w| {
+            Ok(write!(w, "{}", call.type_arguments)?)
+        })?;
+    }
+    show_arguments("Argument", &call.arguments, inputs, split_coins)
+}
+
+#[inline(never)]
+fn show_package(package: &Package) -> Option<()> {
+    scroller("Modules", |w| Ok(write!(w, "{}", package.modules)?))?;
+    scroller_paginated("Package Digest", |w| {
+        Ok(write!(w, "0x{}", HexSlice(&package.digest))?)
+    })
+}
+
+// Names a command that can't be clear signed, for the blind signing warning
+fn describe_command(
+    w: &mut impl Write,
+    command: &Command,
+    reason: SuiStatusWords,
+    recipients: usize,
+) -> core::fmt::Result {
+    match (command, reason) {
+        (Command::MoveCall(call), _) => write!(
+            w,
+            "MoveCall {}::{}::{}",
+            ShortAddress(&call.package),
+            call.module,
+            call.function
+        ),
+        (_, SuiStatusWords::TooManyRecipients) => write!(w, "{} recipients", recipients + 1),
+        (_, SuiStatusWords::AmountOverflow) => w.write_str("Total amount overflows"),
+        (Command::TransferObject(_, _), _) => w.write_str("TransferObjects"),
+        (Command::SplitCoins(_, _), _) => w.write_str("SplitCoins of a coin other than gas"),
+        (Command::MergeCoins(_, _), _) => w.write_str("MergeCoins"),
+        (Command::Publish(_), _) => w.write_str("Publish"),
+        (Command::MakeMoveVec(_, _), _) => w.write_str("MakeMoveVec"),
+        (Command::Upgrade(_, _, _), _) => w.write_str("Upgrade"),
+    }
+}
+
+#[inline(never)]
+fn show_command(
+    command: &Command,
+    inputs: &[CallArg],
+    split_coins: &[SplitCoin],
+    upgrade_policy: Option<u8>,
+) -> Option<()> {
+    match command {
+        Command::MoveCall(call) => show_move_call(call, inputs, split_coins),
+        Command::TransferObject(objects, recipient) => {
+            scroller("Transfer Objects", |w| Ok(write!(w, "{}", objects.len())?))?;
+            show_arguments("Object", objects, inputs, split_coins)?;
+            scroller_paginated("To", |w| {
+                Ok(write_argument(w, recipient, inputs, split_coins)?)
+            })
+        }
+        Command::SplitCoins(_, _) => Some(()),
+        Command::MergeCoins(destination, coins) => {
+            scroller("Merge Coins", |w| Ok(write!(w, "{}", coins.len())?))?;
+            scroller_paginated("Into", |w| {
+                Ok(write_argument(w, destination, inputs, split_coins)?)
+            })
+        }
+        Command::Publish(package) => {
+            scroller("Publish", |w| Ok(write!(w, "Package")?))?;
+            show_package(package)
+        }
+        Command::MakeMoveVec(type_tag, elements) => {
+            scroller("Make Vector", |w| Ok(write!(w, "{}", elements.len())?))?;
+            if let Some(type_tag) = type_tag {
+                scroller_paginated("Element Type", |w| Ok(write!(w, "{type_tag}")?))?;
+            }
+            Some(())
+        }
+        Command::Upgrade(package, package_id, ticket) => {
+            scroller_paginated("Upgrade", |w| Ok(write!(w, "0x{}", HexSlice(package_id))?))?;
+            show_package(package)?;
+            scroller_paginated("Upgrade Ticket", |w| {
+                Ok(write_argument(w, ticket, inputs, split_coins)?)
+            })?;
+            scroller("Policy", |w| match upgrade_policy {
+                Some(policy) => Ok(write_upgrade_policy(w, policy)?),
+                None => Ok(write!(w, "Unknown")?),
+            })
+        }
+    }
+}
+
+// The kind of transaction, and whether any command uses the gas coin
+type ProgrammableSummary = (TxType, bool);
+
+impl<const PROMPT: bool> HasOutput<ProgrammableTransaction<PROMPT>>
+    for ProgrammableTransaction<PROMPT>
+{
+    type Output = ProgrammableSummary;
+}
+
+impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<ProgrammableTransaction<PROMPT>, BS>
+    for ProgrammableTransaction<PROMPT>
+{
+    type State<'c> = impl Future<Output = Self::Output> + 'c where BS: 'c;
+    fn parse<'a: 'c, 'b: 'c, 'c>(&'b self, input: &'a mut BS) -> Self::State<'c> {
+        async move {
+            let mut inputs: ArrayVec<CallArg, INPUT_ARRAY_LENGTH> = ArrayVec::new();
+
+            // Handle inputs
+            {
+                let length =
                     <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
 
                 trace!("ProgrammableTransaction: Inputs: {}", length);
-                for i in 0..length {
+                for _ in 0..length {
                     let arg = <DefaultInterp as AsyncParser<CallArgSchema, BS>>::parse(
                         &DefaultInterp,
                         input,
                     )
                     .await;
-                    match arg {
-                        CallArg::RecipientAddress(addr) => match recipient {
-                            None => {
-                                recipient = Some(addr);
-                                recipient_index = Some(i);
-                            }
-                            // Reject on multiple RecipientAddress(s)
-                            _ => {
-                                reject_on(
-                                    core::file!(),
-                                    core::line!(),
-                                    SyscallError::NotSupported as u16,
-                                )
-                                .await
-                            }
-                        },
-                        CallArg::Amount(amt) =>
-                        {
-                            #[allow(clippy::single_match)]
-                            match amounts.try_push((amt, i)) {
-                                Err(_) => {
-                                    reject_on(
-                                        core::file!(),
-                                        core::line!(),
-                                        SyscallError::NotSupported as u16,
-                                    )
-                                    .await
-                                }
-                                _ => {}
-                            }
-                        }
-                        _ => {}
+                    if inputs.try_push(arg).is_err() {
+                        reject_for::<()>(
+                            core::file!(),
+                            core::line!(),
+                            SuiStatusWords::TooLarge,
+                            |w| write!(w, "Over {INPUT_ARRAY_LENGTH} inputs"),
+                        )
+                        .await;
                     }
                 }
             }
 
-            if recipient_index.is_none() || amounts.is_empty() {
-                reject_on::<()>(
-                    core::file!(),
-                    core::line!(),
-                    SyscallError::NotSupported as u16,
-                )
-                .await;
-            }
-
-            let mut verified_recipient = false;
+            // Every transfer of SUI gets its own recipient, even when the address repeats
+            let mut recipients: ArrayVec<(SuiAddressRaw, TransferAmount), SPLIT_COIN_ARRAY_LENGTH> =
+                ArrayVec::new();
             let mut total_amount: u64 = 0;
+            let mut stake: Option<(SuiAddressRaw, u64)> = None;
+            let mut withdrawal: Option<SuiAddressRaw> = None;
+            let mut split_coins: ArrayVec<SplitCoin, SPLIT_COIN_ARRAY_LENGTH> = ArrayVec::new();
+            // Policies of the UpgradeTickets created by authorize_upgrade calls, by command
+            let mut upgrade_policies: ArrayVec<(u16, u8), UPGRADE_ARRAY_LENGTH> = ArrayVec::new();
+            let mut is_programmable = false;
+            let mut gas_coin_used = false;
             // Handle commands
             {
                 let length =
                     <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, input).await;
                 trace!("ProgrammableTransaction: Commands: {}", length);
-                for _ in 0..length {
+                for command_index in 0..length {
+                    let command_index = command_index as u16;
                     let c = <DefaultInterp as AsyncParser<CommandSchema, BS>>::parse(
                         &DefaultInterp,
                         input,
                     )
                     .await;
-                    match c {
-                        Command::TransferObject(_nested_results, recipient_input) => {
-                            if verified_recipient {
-                                // Reject more than one TransferObject(s)
-                                reject_on::<()>(
-                                    core::file!(),
-                                    core::line!(),
-                                    SyscallError::NotSupported as u16,
-                                )
-                                .await;
+                    gas_coin_used |= uses_gas_coin(&c);
+                    let mut upgrade_policy = None;
+                    let mut is_staking = false;
+                    let rejection = match &c {
+                        Command::MoveCall(call) => {
+                            // Only the first stake and withdrawal are summarized, any others
+                            // are reviewed as plain Move calls
+                            let validator = match stake {
+                                None => add_stake_validator(call, &inputs, &split_coins),
+                                Some(_) => None,
+                            };
+                            let staked_sui = match withdrawal {
+                                None => withdraw_stake_object(call, &inputs),
+                                Some(_) => None,
+                            };
+                            is_staking = validator.is_some() || staked_sui.is_some();
+                            if staked_sui.is_some() {
+                                withdrawal = staked_sui;
                             }
-                            match recipient_input {
-                                Argument::Input(inp_index) => {
-                                    if Some(inp_index as u32) != recipient_index {
-                                        trace!("TransferObject recipient mismatch");
-                                        reject_on::<()>(
-                                            core::file!(),
-                                            core::line!(),
-                                            SyscallError::NotSupported as u16,
-                                        )
-                                        .await;
+                            if is_call_to(
+                                call,
+                                &SUI_FRAMEWORK_ADDRESS,
+                                "package",
+                                "authorize_upgrade",
+                            ) {
+                                if let Some(Argument::Input(inp_index)) = call.arguments.get(1) {
+                                    if let Some(CallArg::OtherPure(policy)) =
+                                        inputs.get(*inp_index as usize)
+                                    {
+                                        if let [policy] = policy.as_slice() {
+                                            let _ =
+                                                upgrade_policies.try_push((command_index, *policy));
+                                        }
                                     }
-                                    verified_recipient = true;
                                 }
-                                _ => {
-                                    reject_on(
-                                        core::file!(),
-                                        core::line!(),
-                                        SyscallError::NotSupported as u16,
-                                    )
-                                    .await
+                            }
+                            match use_arguments(&call.arguments, &inputs, &mut split_coins) {
+                                Some(amount) => {
+                                    if let Some(validator) = validator {
+                                        stake = Some((validator, amount));
+                                    }
+                                    None
                                 }
+                                None => Some(SuiStatusWords::UnsupportedArgument),
                             }
                         }
-                        Command::SplitCoins(coin, input_indices) => {
-                            match coin {
-                                Argument::GasCoin => {}
+                        Command::TransferObject(objects, recipient_input) => {
+                            let from_split = objects
+                                .iter()
+                                .filter(|obj| is_split_coin(obj, &split_coins))
+                                .count();
+                            let recipient_address = match recipient_input {
+                                Argument::Input(inp_index) => {
+                                    match inputs.get(*inp_index as usize) {
+                                        Some(CallArg::RecipientAddress(addr)) => Some(*addr),
+                                        _ => None,
+                                    }
+                                }
+                                _ => None,
+                            };
+                            match (
+                                recipient_address,
+                                use_arguments(objects, &inputs, &mut split_coins),
+                            ) {
+                                (_, None) => Some(SuiStatusWords::UnsupportedArgument),
+                                (Some(_), Some(_)) if objects.is_empty() => {
+                                    Some(SuiStatusWords::UnsupportedTransfer)
+                                }
+                                // Sending the gas coin itself sends the entire balance
+                                (Some(addr), Some(_))
+                                    if matches!(objects.as_slice(), [Argument::GasCoin])
+                                        && recipients.is_empty() =>
+                                {
+                                    recipients.push((addr, TransferAmount::EntireBalance));
+                                    None
+                                }
+                                // Sending the gas coin along with other objects is not supported
+                                (Some(_), Some(_)) if from_split == 0 => objects
+                                    .iter()
+                                    .any(|obj| matches!(obj, Argument::GasCoin))
+                                    .then_some(SuiStatusWords::UnsupportedTransfer),
+                                (Some(addr), Some(amount))
+                                    if from_split == objects.len()
+                                        && !matches!(
+                                            recipients.first(),
+                                            Some((_, TransferAmount::EntireBalance))
+                                        ) =>
+                                {
+                                    match total_amount.checked_add(amount) {
+                                        Some(total) => {
+                                            total_amount = total;
+                                            recipients
+                                                .try_push((addr, TransferAmount::Exact(amount)))
+                                                .err()
+                                                .map(|_| SuiStatusWords::TooManyRecipients)
+                                        }
+                                        None => Some(SuiStatusWords::AmountOverflow),
+                                    }
+                                }
+                                // Reject mixing SUI with other objects, sending the entire
+                                // balance alongside other transfers of SUI, and recipients we
+                                // can't show
                                 _ => {
-                                    reject_on(
-                                        core::file!(),
-                                        core::line!(),
-                                        SyscallError::NotSupported as u16,
-                                    )
-                                    .await
+                                    trace!("TransferObject not supported");
+                                    Some(SuiStatusWords::UnsupportedTransfer)
                                 }
                             }
-                            for arg in &input_indices {
-                                match arg {
-                                    Argument::Input(inp_index) => {
-                                        for (amt, ix) in &amounts {
-                                            if *ix == (*inp_index as u32) {
-                                                match total_amount.checked_add(*amt) {
-                                                    Some(t) => total_amount = t,
-                                                    None => {
-                                                        reject_on(
-                                                            core::file!(),
-                                                            core::line!(),
-                                                            SyscallError::InvalidParameter as u16,
-                                                        )
-                                                        .await
-                                                    }
+                        }
+                        Command::SplitCoins(coin, input_indices) => {
+                            if !matches!(coin, Argument::GasCoin) {
+                                Some(SuiStatusWords::UnsupportedCommand)
+                            } else {
+                                for (index, arg) in input_indices.iter().enumerate() {
+                                    let amount = match arg {
+                                        Argument::Input(inp_index) => {
+                                            match inputs.get(*inp_index as usize) {
+                                                Some(CallArg::Amount(amt)) => *amt,
+                                                _ => {
+                                                    reject_for(
+                                                        core::file!(),
+                                                        core::line!(),
+                                                        SuiStatusWords::UnsupportedArgument,
+                                                        |w| {
+                                                            w.write_str(
+                                                                "Split amount not a u64 input",
+                                                            )
+                                                        },
+                                                    )
+                                                    .await
                                                 }
                                             }
                                         }
-                                    }
-                                    _ => {
-                                        reject_on(
+                                        _ => {
+                                            reject_for(
+                                                core::file!(),
+                                                core::line!(),
+                                                SuiStatusWords::UnsupportedArgument,
+                                                |w| w.write_str("Split amount not a u64 input"),
+                                            )
+                                            .await
+                                        }
+                                    };
+                                    if split_coins
+                                        .try_push(SplitCoin {
+                                            command: command_index,
+                                            index: index as u16,
+                                            amount,
+                                            used: false,
+                                        })
+                                        .is_err()
+                                    {
+                                        reject_for::<()>(
                                             core::file!(),
                                             core::line!(),
-                                            SyscallError::NotSupported as u16,
+                                            SuiStatusWords::TooLarge,
+                                            |w| {
+                                                write!(
+                                                    w,
+                                                    "Over {SPLIT_COIN_ARRAY_LENGTH} split coins"
+                                                )
+                                            },
                                         )
-                                        .await
+                                        .await;
                                     }
                                 }
+                                None
                             }
                         }
+                        Command::MergeCoins(destination, coins) => {
+                            let valid = use_arguments(
+                                core::slice::from_ref(destination),
+                                &inputs,
+                                &mut split_coins,
+                            )
+                            .is_some()
+                                && use_arguments(coins, &inputs, &mut split_coins).is_some();
+                            (!valid).then_some(SuiStatusWords::UnsupportedArgument)
+                        }
+                        Command::Publish(_) => None,
+                        Command::MakeMoveVec(_, elements) => {
+                            use_arguments(elements, &inputs, &mut split_coins)
+                                .is_none()
+                                .then_some(SuiStatusWords::UnsupportedArgument)
+                        }
+                        Command::Upgrade(_, _, ticket) => {
+                            upgrade_policy = result_command(ticket).and_then(|cmd| {
+                                upgrade_policies
+                                    .iter()
+                                    .find(|(c, _)| *c == cmd)
+                                    .map(|(_, policy)| *policy)
+                            });
+                            use_arguments(core::slice::from_ref(ticket), &inputs, &mut split_coins)
+                                .is_none()
+                                .then_some(SuiStatusWords::UnsupportedArgument)
+                        }
+                    };
+                    if let Some(reason) = rejection {
+                        reject_for::<()>(core::file!(), core::line!(), reason, |w| {
+                            describe_command(w, &c, reason, recipients.len())
+                        })
+                        .await;
+                    }
+
+                    // Transfers of SUI and staking are summarized at the end, everything else is
+                    // shown as is
+                    let is_summarized = match &c {
+                        Command::MoveCall(_) => is_staking,
+                        Command::SplitCoins(_, _) => true,
+                        Command::TransferObject(objects, _) => {
+                            objects.first().map_or(false, |obj| {
+                                matches!(obj, Argument::GasCoin) || is_split_coin(obj, &split_coins)
+                            })
+                        }
+                        // Gathering the user's coins into the gas coin only adds to what is sent,
+                        // as long as the gas coin is theirs too
+                        Command::MergeCoins(Argument::GasCoin, coins) => {
+                            self.sender_owns_gas
+                                && coins.iter().all(|coin| is_object_input(coin, &inputs))
+                        }
+                        _ => false,
+                    };
+                    if !is_summarized {
+                        is_programmable = true;
+                        if PROMPT
+                            && show_command(&c, &inputs, &split_coins, upgrade_policy).is_none()
+                        {
+                            reject::<()>(StatusWords::UserCancelled as u16).await;
+                        }
                     }
                 }
             }
 
-            if !verified_recipient {
-                reject_on::<()>(
+            // Every coin split off the gas coin must be accounted for, so that the amount shown
+            // is the amount that reaches the recipients
+            if split_coins.iter().any(|c| !c.used) {
+                reject_for::<()>(
+                    core::file!(),
+                    core::line!(),
+                    SuiStatusWords::UnusedSplitCoins,
+                    |w| {
+                        write!(
+                            w,
+                            "{} split coins not sent",
+                            split_coins.iter().filter(|c| !c.used).count()
+                        )
+                    },
+                )
+                .await;
+            }
+            if recipients.is_empty() && stake.is_none() && withdrawal.is_none() && !is_programmable
+            {
+                reject_for::<()>(
                     core::file!(),
                     core::line!(),
-                    SyscallError::NotSupported as u16,
+                    SuiStatusWords::NothingToReview,
+                    |w| w.write_str("No transfer, stake or command"),
                 )
                 .await;
             }
@@ -483,29 +1668,59 @@ impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<ProgrammableTransacti
             if PROMPT
                 && Option::<()>::is_none(
                     &try {
-                        scroller_paginated("To", |w| {
-                            Ok(write!(
-                                w,
-                                "0x{}",
-                                HexSlice(&recipient.ok_or(ScrollerError)?)
-                            )?)
-                        })?;
-
-                        let (quotient, remainder_str) = get_amount_in_decimals(total_amount);
-                        scroller_paginated("Amount", |w| {
-                            Ok(write!(w, "SUI {quotient}.{}", remainder_str.as_str())?)
-                        })?;
+                        let numbered = recipients.len() > 1;
+                        for (i, (recipient, amount)) in recipients.iter().enumerate() {
+                            show_recipient(
+                                numbered.then_some(i + 1),
+                                recipient,
+                                *amount,
+                                self.sender_owns_gas,
+                            )?;
+                        }
+                        if numbered {
+                            let (quotient, remainder_str) = get_amount_in_decimals(total_amount);
+                            scroller("Total Amount", |w| {
+                                Ok(write!(w, "SUI {quotient}.{}", remainder_str.as_str())?)
+                            })?;
+                        }
+                        if let Some((validator, amount)) = stake {
+                            let (quotient, remainder_str) = get_amount_in_decimals(amount);
+                            scroller("Stake Amount", |w| {
+                                Ok(write!(w, "SUI {quotient}.{}", remainder_str.as_str())?)
+                            })?;
+                            scroller_paginated("Validator", |w| {
+                                Ok(write!(w, "0x{}", HexSlice(&validator))?)
+                            })?;
+                        }
+                        if let Some(staked_sui) = withdrawal {
+                            scroller_paginated("Staked SUI", |w| {
+                                Ok(write!(w, "0x{}", HexSlice(&staked_sui))?)
+                            })?;
+                        }
                     },
                 )
             {
                 reject::<()>(StatusWords::UserCancelled as u16).await;
             }
+
+            let tx_type = match (
+                is_programmable,
+                !recipients.is_empty(),
+                stake.is_some(),
+                withdrawal.is_some(),
+            ) {
+                (false, true, false, false) => TxType::SuiTransfer,
+                (false, false, true, false) => TxType::Stake,
+                (false, false, false, true) => TxType::WithdrawStake,
+                _ => TxType::Programmable,
+            };
+            (tx_type, gas_coin_used)
         }
     }
 }
 
 impl<const PROMPT: bool> HasOutput<TransactionKind<PROMPT>> for TransactionKind<PROMPT> {
-    type Output = ();
+    type Output = ProgrammableSummary;
 }
 
 impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<TransactionKind<PROMPT>, BS>
@@ -522,15 +1737,21 @@ impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<TransactionKind<PROMP
                     <ProgrammableTransaction<PROMPT> as AsyncParser<
                         ProgrammableTransaction<PROMPT>,
                         BS,
-                    >>::parse(&ProgrammableTransaction::<PROMPT>, input)
-                    .await;
+                    >>::parse(
+                        &ProgrammableTransaction::<PROMPT> {
+                            sender_owns_gas: self.sender_owns_gas,
+                        },
+                        input,
+                    )
+                    .await
                 }
                 _ => {
                     trace!("TransactionKind: {}", enum_variant);
-                    reject_on(
+                    reject_for(
                         core::file!(),
                         core::line!(),
-                        SyscallError::NotSupported as u16,
+                        SuiStatusWords::UnsupportedTransactionKind,
+                        |w| write!(w, "Transaction kind {enum_variant}"),
                     )
                     .await
                 }
@@ -562,8 +1783,41 @@ fn get_amount_in_decimals(amount: u64) -> (u64, ArrayString<12>) {
     (quotient, remainder_str)
 }
 
+// When a transaction may be executed, and for ValidDuring, the first bytes of the chain identifier
+// that Sui shows
+#[derive(Clone, Copy)]
+pub enum Expiration {
+    None,
+    Epoch(u64),
+    ValidDuring {
+        min_epoch: Option<u64>,
+        max_epoch: Option<u64>,
+        min_timestamp: Option<u64>,
+        max_timestamp: Option<u64>,
+        chain: [u8; 4],
+    },
+}
+
+// BCS encodes an Option as a vector of at most one element
+async fn parse_optional_u64<BS: Readable>(input: &mut BS) -> Option<u64> {
+    let [length]: [u8; 1] = input.read().await;
+    match length {
+        0 => None,
+        1 => Some(<DefaultInterp as AsyncParser<U64LE, BS>>::parse(&DefaultInterp, input).await),
+        tag => {
+            reject_for(
+                core::file!(),
+                core::line!(),
+                SuiStatusWords::UnsupportedExpiration,
+                |w| write!(w, "Expiration option tag {tag}"),
+            )
+            .await
+        }
+    }
+}
+
 impl HasOutput<TransactionExpiration> for DefaultInterp {
-    type Output = ();
+    type Output = Expiration;
 }
 
 impl<BS: Clone + Readable> AsyncParser<TransactionExpiration, BS> for DefaultInterp {
@@ -575,16 +1829,48 @@ impl<BS: Clone + Readable> AsyncParser<TransactionExpiration, BS> for DefaultInt
             match enum_variant {
                 0 => {
                     trace!("TransactionExpiration: None");
+                    Expiration::None
                 }
                 1 => {
                     trace!("TransactionExpiration: Epoch");
-                    <DefaultInterp as AsyncParser<EpochId, BS>>::parse(&DefaultInterp, input).await;
+                    Expiration::Epoch(
+                        <DefaultInterp as AsyncParser<EpochId, BS>>::parse(&DefaultInterp, input)
+                            .await,
+                    )
+                }
+                2 => {
+                    trace!("TransactionExpiration: ValidDuring");
+                    let min_epoch = parse_optional_u64(input).await;
+                    let max_epoch = parse_optional_u64(input).await;
+                    let min_timestamp = parse_optional_u64(input).await;
+                    let max_timestamp = parse_optional_u64(input).await;
+                    let digest = <DefaultInterp as AsyncParser<ChainIdentifier, BS>>::parse(
+                        &DefaultInterp,
+                        input,
+                    )
+                    .await;
+                    <DefaultInterp as AsyncParser<ExpirationNonce, BS>>::parse(
+                        &DefaultInterp,
+                        input,
+                    )
+                    .await;
+                    let mut chain = [0; 4];
+                    // Skip the length of the digest
+                    chain.copy_from_slice(&digest[1..5]);
+                    Expiration::ValidDuring {
+                        min_epoch,
+                        max_epoch,
+                        min_timestamp,
+                        max_timestamp,
+                        chain,
+                    }
                 }
                 _ => {
-                    reject_on(
+                    reject_for(
                         core::file!(),
                         core::line!(),
-                        SyscallError::NotSupported as u16,
+                        SuiStatusWords::UnsupportedExpiration,
+                        |w| write!(w, "Expiration kind {enum_variant}"),
                     )
                     .await
                 }
@@ -593,30 +1879,116 @@ impl<BS: Clone + Readable> AsyncParser<TransactionExpiration, BS> for DefaultInt
     }
 }
 
-const fn gas_data_parser<BS: Clone + Readable, const PROMPT: bool>(
-) -> impl AsyncParser<GasData<PROMPT>, BS> + HasOutput<GasData<PROMPT>, Output = ()> {
-    Action(
-        (
-            SubInterp(object_ref_parser()),
-            DefaultInterp,
-            DefaultInterp,
-            DefaultInterp,
-        ),
-        |(_, _sender, _gas_price, gas_budget): (_, _, u64, u64)| {
+fn show_expiration(expiration: &Expiration) -> Option<()> {
+    match expiration {
+        Expiration::None => {}
+        Expiration::Epoch(epoch) => {
+            scroller("Expires", |w| Ok(write!(w, "After epoch {epoch}")?))?;
+        }
+        Expiration::ValidDuring {
+            min_epoch,
+            max_epoch,
+            min_timestamp,
+            max_timestamp,
+            chain,
+        } => {
+            if let Some(epoch) = min_epoch {
+                scroller("Valid From", |w| Ok(write!(w, "Epoch {epoch}")?))?;
+            }
+            if let Some(epoch) = max_epoch {
+                scroller("Expires", |w| Ok(write!(w, "After epoch {epoch}")?))?;
+            }
+            if let Some(timestamp) = min_timestamp {
+                scroller("Valid From", |w| Ok(write!(w, "{}", UtcTime(*timestamp))?))?;
+            }
+            if let Some(timestamp) = max_timestamp {
+                scroller("Valid Until", |w| Ok(write!(w, "{}", UtcTime(*timestamp))?))?;
+            }
+            scroller("Chain", |w| Ok(write!(w, "{}", HexSlice(chain))?))?;
+        }
+    }
+    Some(())
+}
+
+// The gas owner, price and budget
+type GasSummary = (SuiAddressRaw, u64, u64);
+
+impl<const PROMPT: bool> HasOutput<GasData<PROMPT>> for GasData<PROMPT> {
+    type Output = GasSummary;
+}
+
+impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<GasData<PROMPT>, BS>
+    for GasData<PROMPT>
+{
+    type State<'c> = impl Future<Output = Self::Output> + 'c where BS: 'c;
+    fn parse<'a: 'c, 'b: 'c, 'c>(&'b self, input: &'a mut BS) -> Self::State<'c> {
+        async move {
+            // The payment comes before the budget, so it's parsed again to be shown after it
+            let payment = input.clone();
+            <SubInterp<_> as AsyncParser<GasPayment, BS>>::parse(
+                &SubInterp(object_ref_parser()),
+                input,
+            )
+            .await;
+            let owner =
+                <DefaultInterp as AsyncParser<SuiAddress, BS>>::parse(&DefaultInterp, input).await;
+            let gas_price =
+                <DefaultInterp as AsyncParser<Amount, BS>>::parse(&DefaultInterp, input).await;
+            let gas_budget =
+                <DefaultInterp as AsyncParser<Amount, BS>>::parse(&DefaultInterp, input).await;
             // Gas price is per gas amount. Gas budget is total, reflecting the amount of gas *
             // gas price. We only care about the total, not the price or amount in isolation , so we
-            // just ignore that field.
+            // only show the price to a gas sponsor, or in expert mode.
             //
             // C.F. https://github.com/MystenLabs/sui/pull/8676
             if PROMPT {
-                let (quotient, remainder_str) = get_amount_in_decimals(gas_budget);
-                scroller("Max Gas", |w| {
-                    Ok(write!(w, "SUI {}.{}", quotient, remainder_str.as_str())?)
-                })?
+                if show_max_gas(gas_budget).is_none() {
+                    reject::<()>(StatusWords::UserCancelled as u16).await;
+                }
+                if Settings.get().expert_mode {
+                    show_gas_payment(payment, gas_price).await;
+                }
             }
-            Some(())
-        },
-    )
+            (owner, gas_price, gas_budget)
+        }
+    }
+}
+
+async fn show_gas_payment<BS: Clone + Readable>(mut input: BS, gas_price: u64) {
+    if scroller("Gas Price", |w| Ok(write!(w, "{gas_price} MIST")?)).is_none() {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    }
+    let coins =
+        <DefaultInterp as AsyncParser<ULEB128, BS>>::parse(&DefaultInterp, &mut input).await;
+    if scroller("Gas Coins", |w| Ok(write!(w, "{coins}")?)).is_none() {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    }
+    for i in 1..=coins {
+        let object_id =
+            <DefaultInterp as AsyncParser<ObjectID, BS>>::parse(&DefaultInterp, &mut input).await;
+        <DefaultInterp as AsyncParser<SequenceNumber, BS>>::parse(&DefaultInterp, &mut input).await;
+        <DefaultInterp as AsyncParser<ObjectDigest, BS>>::parse(&DefaultInterp, &mut input).await;
+        let mut title: ArrayString<24> = ArrayString::new();
+        let shown: Option<()> = try {
+            write!(title, "Gas Coin {i}").ok()?;
+            scroller_paginated(&title, |w| Ok(write!(w, "0x{}", HexSlice(&object_id))?))?;
+        };
+        if shown.is_none() {
+            reject::<()>(StatusWords::UserCancelled as u16).await;
+        }
+    }
+}
+
+// Shows the gas budget, and warns when it is over the threshold chosen in the settings
+fn show_max_gas(gas_budget: u64) -> Option<()> {
+    let (quotient, remainder_str) = get_amount_in_decimals(gas_budget);
+    scroller("Max Gas", |w| {
+        Ok(write!(w, "SUI {}.{}", quotient, remainder_str.as_str())?)
+    })?;
+    if matches!(Settings.get().gas_warning_threshold(), Some(threshold) if gas_budget > threshold) {
+        scroller("WARNING", |w| Ok(write!(w, "High gas budget!")?))?;
+    }
+    Some(())
 }
 
 const fn object_ref_parser<BS: Readable>(
@@ -624,30 +1996,79 @@ const fn object_ref_parser<BS: Readable>(
     Action((DefaultInterp, DefaultInterp, DefaultInterp), |_| Some(()))
 }
 
-const fn intent_parser<BS: Readable>(
-) -> impl AsyncParser<Intent, BS> + HasOutput<Intent, Output = ()> {
-    Action((DefaultInterp, DefaultInterp, DefaultInterp), |_| {
-        trace!("Intent Ok");
-        Some(())
-    })
+const INTENT_LENGTH: usize = 3;
+
+// Decodes the intent at the start of a message, and rejects it unless it is for the Sui app
+// and has the scope of what the instruction signs
+//
+// Every value we accept is below 0x80, so each ULEB128 takes exactly one byte
+async fn check_intent<BS: Readable>(input: &mut BS, expected_scope: IntentScope) {
+    let [scope, version, app_id]: [u8; INTENT_LENGTH] = input.read().await;
+    trace!("Intent: {} {} {}", scope, version, app_id);
+    if IntentScope::try_from(scope as u32).ok() != Some(expected_scope) {
+        reject::<()>(SuiStatusWords::BadIntentScope as u16).await;
+    }
+    if IntentVersion::try_from(version as u32).ok() != Some(IntentVersion::V0) {
+        reject::<()>(SuiStatusWords::BadIntentVersion as u16).await;
+    }
+    if AppId::try_from(app_id as u32).ok() != Some(AppId::Sui) {
+        reject::<()>(SuiStatusWords::BadAppId as u16).await;
+    }
+}
+
+// What the parse of a transaction found, for the checks and screens that don't belong to any one
+// part of it
+#[derive(Clone, Copy)]
+pub struct TransactionSummary {
+    pub tx_type: TxType,
+    pub gas_coin_used: bool,
+    pub sender: SuiAddressRaw,
+    pub gas_owner: SuiAddressRaw,
+    pub gas_price: u64,
+    pub gas_budget: u64,
+    pub expiration: Expiration,
 }
 
 const fn transaction_data_v1_parser<BS: Clone + Readable, const PROMPT: bool>(
-) -> impl AsyncParser<TransactionDataV1<PROMPT>, BS> + HasOutput<TransactionDataV1<PROMPT>, Output = ()>
-{
+    sender_owns_gas: bool,
+) -> impl AsyncParser<TransactionDataV1<PROMPT>, BS>
+       + HasOutput<TransactionDataV1<PROMPT>, Output = TransactionSummary> {
     Action(
         (
-            TransactionKind::<PROMPT>,
+            TransactionKind::<PROMPT> { sender_owns_gas },
             DefaultInterp,
-            gas_data_parser::<_, PROMPT>(),
+            GasData::<PROMPT>,
             DefaultInterp,
         ),
-        |_| Some(()),
+        |((tx_type, gas_coin_used), sender, (gas_owner, gas_price, gas_budget), expiration): (
+            _,
+            SuiAddressRaw,
+            _,
+            Expiration,
+        )| {
+            if PROMPT {
+                if gas_owner != sender {
+                    scroller_paginated("Gas paid by", |w| {
+                        Ok(write!(w, "0x{}", HexSlice(&gas_owner))?)
+                    })?
+                }
+                show_expiration(&expiration)?
+            }
+            Some(TransactionSummary {
+                tx_type,
+                gas_coin_used,
+                sender,
+                gas_owner,
+                gas_price,
+                gas_budget,
+                expiration,
+            })
+        },
     )
 }
 
 impl<const PROMPT: bool> HasOutput<TransactionData<PROMPT>> for TransactionData<PROMPT> {
-    type Output = ();
+    type Output = TransactionSummary;
 }
 
 impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<TransactionData<PROMPT>, BS>
@@ -661,13 +2082,16 @@ impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<TransactionData<PROMP
             match enum_variant {
                 0 => {
                     trace!("TransactionData: V1");
-                    transaction_data_v1_parser::<_, PROMPT>().parse(input).await;
+                    transaction_data_v1_parser::<_, PROMPT>(self.sender_owns_gas)
+                        .parse(input)
+                        .await
                 }
                 _ => {
-                    reject_on(
+                    reject_for(
                         core::file!(),
                         core::line!(),
-                        SyscallError::NotSupported as u16,
+                        SuiStatusWords::UnsupportedTransactionVersion,
+                        |w| write!(w, "Transaction version {enum_variant}"),
                     )
                     .await
                 }
@@ -676,12 +2100,177 @@ impl<BS: Clone + Readable, const PROMPT: bool> AsyncParser<TransactionData<PROMP
     }
 }
 
-const fn tx_parser<BS: Clone + Readable, const PROMPT: bool>(
-) -> impl AsyncParser<IntentMessage<PROMPT>, BS> + HasOutput<IntentMessage<PROMPT>, Output = ()> {
-    Action((intent_parser(), TransactionData::<PROMPT>), |_| Some(()))
+// The account that will sign: the path the host asked for, and its address
+pub struct Signer {
+    pub path: ArrayVec<u32, 10>,
+    pub address: SuiAddressRaw,
+}
+
+// The signer for the path in the given parameter, once the path is confirmed if it is unusual
+async fn parse_signer(mut bs: ByteStream) -> Signer {
+    NoinlineFut(async move {
+        let path = BIP_PATH_PARSER.parse(&mut bs).await;
+        confirm_path(&path).await;
+        match with_public_key(&path, |address| Some(*address.address())) {
+            Some(address) => Signer { path, address },
+            None => reject(SyscallError::Unspecified as u16).await,
+        }
+    })
+    .await
 }
 
-pub async fn sign_apdu(io: HostIO, settings: Settings) {
+async fn show_signer(signer: &Signer) {
+    let shown: Option<()> = try {
+        scroller_paginated("From", |w| {
+            Ok(write!(w, "0x{}", HexSlice(&signer.address))?)
+        })?;
+        scroller_paginated("Path", |w| {
+            Ok(write!(w, "{}", DerivationPath(&signer.path))?)
+        })?;
+    };
+    if shown.is_none() {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    }
+}
+
+async fn hash_stream(hasher: &mut Blake2b, txn: &mut ByteStream, length: usize) {
+    const CHUNK_SIZE: usize = 128;
+    let (chunks, rem) = (length / CHUNK_SIZE, length % CHUNK_SIZE);
+    for _ in 0..chunks {
+        let b: [u8; CHUNK_SIZE] = txn.read().await;
+        hasher.update(&b);
+    }
+    for _ in 0..rem {
+        let b: [u8; 1] = txn.read().await;
+        hasher.update(&b);
+    }
+}
+
+async fn hash_input(mut txn: ByteStream, length: usize) -> HexHash<32> {
+    let mut hasher: Blake2b = Hasher::new();
+    hash_stream(&mut hasher, &mut txn, length).await;
+    hasher.finalize()
+}
+
+// Sui's TransactionDigest: the hash of the TransactionData, without the intent, behind its type
+// name
+async fn transaction_digest(mut txn: ByteStream, length: usize) -> [u8; 32] {
+    let mut hasher: Blake2b = Hasher::new();
+    hasher.update(b"TransactionData::");
+    hash_stream(&mut hasher, &mut txn, length).await;
+    hasher.finalize()
+}
+
+// Reviews a transaction that the signer sends, or that is sent by another account if the settings
+// allow it
+async fn review_transaction(
+    txn_start: ByteStream,
+    settings: Settings,
+    signer: &Signer,
+    summary: &TransactionSummary,
+) {
+    // A transaction from another account can't be executed with this signature alone, and may
+    // have been swapped in to get the user to sign something they didn't intend
+    if summary.sender != signer.address && settings.get().reject_sender_mismatch {
+        scroller("WARNING", |w| {
+            Ok(write!(
+                w,
+                "Sender is not the signing account, set Sender Mismatch to Warn to sign anyway"
+            )?)
+        });
+        reject::<()>(SuiStatusWords::SenderMismatch as u16).await;
+    }
+
+    // SUI sent from a gas coin the sender doesn't own is not a transfer of theirs
+    let tx_type = if summary.gas_owner != summary.sender && summary.gas_coin_used {
+        TxType::Programmable
+    } else {
+        summary.tx_type
+    };
+    if match tx_type {
+        TxType::SuiTransfer => scroller("Transfer", |w| Ok(write!(w, "SUI")?)),
+        TxType::Stake => scroller("Stake", |w| Ok(write!(w, "SUI")?)),
+        TxType::WithdrawStake => scroller("Withdraw", |w| Ok(write!(w, "Stake")?)),
+        TxType::Programmable => scroller("Review", |w| Ok(write!(w, "Transaction")?)),
+    }
+    .is_none()
+    {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    };
+    show_signer(signer).await;
+    if summary.sender != signer.address {
+        if scroller_paginated("Sender", |w| {
+            Ok(write!(w, "0x{}", HexSlice(&summary.sender))?)
+        })
+        .is_none()
+        {
+            reject::<()>(StatusWords::UserCancelled as u16).await;
+        }
+        if scroller("WARNING", |w| {
+            Ok(write!(w, "Sender is not the signing account")?)
+        })
+        .is_none()
+        {
+            reject::<()>(StatusWords::UserCancelled as u16).await;
+        }
+    }
+
+    {
+        let mut txn = txn_start;
+        NoinlineFut(async move {
+            trace!("Beginning parse");
+            TransactionData::<true> {
+                sender_owns_gas: summary.gas_owner == summary.sender,
+            }
+            .parse(&mut txn)
+            .await;
+        })
+        .await
+    };
+
+    if final_accept_prompt(&["Sign Transaction?"]).is_none() {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    };
+}
+
+// Reviews signing as the gas owner of a transaction another account sends, which only pays for its
+// gas as long as no command touches the gas coin
+async fn review_sponsorship(signer: &Signer, summary: &TransactionSummary) {
+    if scroller("Sponsor", |w| Ok(write!(w, "Transaction")?)).is_none() {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    }
+    show_signer(signer).await;
+    let shown: Option<()> = try {
+        scroller_paginated("Sponsoring", |w| {
+            Ok(write!(w, "0x{}", HexSlice(&summary.sender))?)
+        })?;
+        show_max_gas(summary.gas_budget)?;
+        scroller("Gas Price", |w| {
+            Ok(write!(w, "{} MIST", summary.gas_price)?)
+        })?;
+        show_expiration(&summary.expiration)?;
+        final_accept_prompt(&["Sign Transaction?"])?;
+    };
+    if shown.is_none() {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    }
+}
+
+// Shows the first thing the check parse couldn't clear sign, if it got that far
+fn show_rejection(
+    rejection: &Option<(SuiStatusWords, ArrayString<REJECTION_DETAIL_LENGTH>)>,
+) -> Option<()> {
+    match rejection {
+        Some((_, detail)) if !detail.is_empty() => {
+            scroller_paginated("Unsupported", |w| Ok(write!(w, "{detail}")?))
+        }
+        _ => Some(()),
+    }
+}
+
+// With serialized set, the signature is returned as Sui serializes it, flag || signature || public
+// key, followed by the transaction digest
+pub async fn sign_apdu(io: HostIO, mut settings: Settings, serialized: bool) {
     let mut input = match io.get_params::<2>() {
         Some(v) => v,
         None => reject(SyscallError::InvalidParameter as u16).await,
@@ -690,98 +2279,273 @@ pub async fn sign_apdu(io: HostIO, settings: Settings) {
     // Read length, and move input[0] by one byte
     let length = usize::from_le_bytes(input[0].read().await);
 
-    let known_txn = {
-        let mut txn = input[0].clone();
-        NoinlineFut(async move {
-            trace!("Beginning check parse");
-            TryFuture(tx_parser::<_, false>().parse(&mut txn))
-                .await
-                .is_some()
-        })
-        .await
-    };
+    // Even blind signing only signs transactions
+    let mut txn_start = input[0].clone();
+    NoinlineFut(check_intent(&mut txn_start, IntentScope::TransactionData)).await;
 
-    if known_txn {
-        if scroller("Transfer", |w| Ok(write!(w, "SUI")?)).is_none() {
-            reject::<()>(StatusWords::UserCancelled as u16).await;
-        };
-        {
-            let mut bs = input[1].clone();
-            NoinlineFut(async move {
-                let path = BIP_PATH_PARSER.parse(&mut bs).await;
-                if !path.starts_with(&BIP32_PREFIX[0..2]) {
-                    reject::<()>(SyscallError::InvalidParameter as u16).await;
-                }
-                if with_public_keys(&path, true, |_, address: &SuiPubKeyAddress| {
-                    try_option(|| -> Option<()> {
-                        scroller_paginated("From", |w| Ok(write!(w, "{address}")?))?;
-                        Some(())
-                    }())
-                })
-                .ok()
-                .is_none()
-                {
-                    reject::<()>(StatusWords::UserCancelled as u16).await;
-                }
-            })
-            .await
-        };
+    let signer = parse_signer(input[1].clone()).await;
 
-        {
-            let mut txn = input[0].clone();
+    unsafe { REJECTION = None };
+    // Only whether the transaction was recognized is kept for signing, not its summary
+    let known_txn = {
+        let checked = {
+            let mut txn = txn_start.clone();
             NoinlineFut(async move {
-                trace!("Beginning parse");
-                tx_parser::<_, true>().parse(&mut txn).await;
+                trace!("Beginning check parse");
+                // Who owns the gas coin only changes what the prompt parse shows
+                TryFuture(
+                    TransactionData::<false> {
+                        sender_owns_gas: true,
+                    }
+                    .parse(&mut txn),
+                )
+                .await
             })
             .await
         };
-
-        if final_accept_prompt(&["Sign Transaction?"]).is_none() {
-            reject::<()>(StatusWords::UserCancelled as u16).await;
-        };
-    } else if settings.get() == 0 {
-        scroller("WARNING", |w| {
-            Ok(write!(
-                w,
-                "Transaction not recognized, enable blind signing to sign unknown transactions"
-            )?)
-        });
-        reject::<()>(SyscallError::NotSupported as u16).await;
-    } else if scroller("WARNING", |w| Ok(write!(w, "Transaction not recognized")?)).is_none() {
-        reject::<()>(StatusWords::UserCancelled as u16).await;
-    }
-
-    // By the time we get here, we've approved and just need to do the signature.
-    NoinlineFut(async move {
-        let mut hasher: Blake2b = Hasher::new();
-        {
-            let mut txn = input[0].clone();
-            const CHUNK_SIZE: usize = 128;
-            let (chunks, rem) = (length / CHUNK_SIZE, length % CHUNK_SIZE);
-            for _ in 0..chunks {
-                let b: [u8; CHUNK_SIZE] = txn.read().await;
-                hasher.update(&b);
+        match checked {
+            Some(summary) => {
+                // A sponsor whose gas coin is spent by the commands has to see them
+                if summary.gas_owner == signer.address
+                    && summary.sender != signer.address
+                    && !summary.gas_coin_used
+                {
+                    review_sponsorship(&signer, &summary).await;
+                } else {
+                    review_transaction(txn_start.clone(), settings, &signer, &summary).await;
+                }
+                true
             }
-            for _ in 0..rem {
-                let b: [u8; 1] = txn.read().await;
-                hasher.update(&b);
+            None => {
+                let rejection = unsafe { REJECTION };
+                if settings.get().blind_signing() == BlindSigning::Disabled {
+                    scroller("WARNING", |w| {
+                        Ok(write!(
+                            w,
+                            "Transaction not recognized, enable blind signing to sign unknown \
+                             transactions"
+                        )?)
+                    });
+                    show_rejection(&rejection);
+                    reject::<()>(
+                        rejection.map_or(SyscallError::NotSupported as u16, |(r, _)| r as u16),
+                    )
+                    .await;
+                } else if Option::<()>::is_none(
+                    &try {
+                        scroller("WARNING", |w| Ok(write!(w, "Transaction not recognized")?))?;
+                        show_rejection(&rejection)?;
+                    },
+                ) {
+                    reject::<()>(StatusWords::UserCancelled as u16).await;
+                }
+                false
             }
         }
-        let hash: HexHash<32> = hasher.finalize();
+    };
+
+    // By the time we get here, we've approved and just need to do the signature.
+    NoinlineFut(async move {
+        let hash = hash_input(input[0].clone(), length).await;
+        // The digest wallets and explorers show, needed only when it will be shown or returned
+        let digest = if !known_txn || serialized {
+            transaction_digest(txn_start, length.saturating_sub(INTENT_LENGTH)).await
+        } else {
+            [0; 32]
+        };
         if !known_txn {
+            let digest_base58 = base58_encode(&digest).unwrap_or_default();
+            if scroller_paginated("Transaction Digest", |w| Ok(write!(w, "{digest_base58}")?))
+                .is_none()
+            {
+                reject::<()>(StatusWords::UserCancelled as u16).await;
+            };
             if scroller("Transaction Hash", |w| Ok(write!(w, "0x{hash}")?)).is_none() {
                 reject::<()>(StatusWords::UserCancelled as u16).await;
             };
             if final_accept_prompt(&["Blind Sign Transaction?"]).is_none() {
                 reject::<()>(StatusWords::UserCancelled as u16).await;
             };
+            // Blind signing that was allowed once is used up
+            if settings.get().blind_signing() == BlindSigning::Once {
+                settings.update(|s| s.set_blind_signing(BlindSigning::Disabled));
+            }
+        }
+        let path = BIP_PATH_PARSER.parse(&mut input[1].clone()).await;
+        if check_path(&path).is_none() {
+            reject::<()>(SyscallError::InvalidParameter as u16).await;
+        }
+        let sig = match sign_digest(&path, &hash.0) {
+            Some(sig) => sig,
+            None => reject(SyscallError::Unspecified as u16).await,
+        };
+        if !serialized {
+            io.result_final(&sig).await;
+            return;
+        }
+        let mut rv = ArrayVec::<u8, 130>::new();
+        if with_public_key(&path, |address| {
+            rv.try_push(SignatureScheme::from_path(&path)? as u8).ok()?;
+            rv.try_extend_from_slice(&sig).ok()?;
+            rv.try_extend_from_slice(address.public_key()).ok()?;
+            rv.try_extend_from_slice(&digest).ok()
+        })
+        .is_none()
+        {
+            reject::<()>(SyscallError::Unspecified as u16).await;
+        }
+        io.result_final(&rv).await;
+    })
+    .await
+}
+
+// Longer messages are shown by their hash, rather than paged through on screen
+const PERSONAL_MESSAGE_TEXT_LENGTH: usize = 1024;
+// Longer messages that are not text are shown by their hash
+const PERSONAL_MESSAGE_HEX_LENGTH: usize = 64;
+const PERSONAL_MESSAGE_CHUNK_LENGTH: usize = 128;
+
+#[derive(Clone, Copy)]
+enum MessageDisplay {
+    Text,
+    Hex,
+    Hash,
+}
+
+fn uleb128_length(mut n: usize) -> usize {
+    let mut length = 1;
+    while n >= 0x80 {
+        n >>= 7;
+        length += 1;
+    }
+    length
+}
+
+// The device font only covers printable ASCII, newlines are shown as spaces
+fn is_printable(b: u8) -> bool {
+    b == b'\n' || (0x20..0x7f).contains(&b)
+}
+
+#[inline(never)]
+fn show_message_chunk(
+    display: MessageDisplay,
+    chunk: &[u8],
+    index: usize,
+    chunks: usize,
+) -> Option<()> {
+    let mut title: ArrayString<24> = ArrayString::new();
+    if chunks > 1 {
+        write!(title, "Message {}/{}", index + 1, chunks).ok()?;
+    } else {
+        write!(title, "Message").ok()?;
+    }
+    scroller_paginated(&title, |w| match display {
+        MessageDisplay::Hex => Ok(write!(w, "0x{}", HexSlice(chunk))?),
+        _ => {
+            for b in chunk {
+                w.write_char(if *b == b'\n' { ' ' } else { char::from(*b) })?;
+            }
+            Ok(())
         }
+    })
+}
+
+pub async fn sign_personal_message_apdu(io: HostIO) {
+    let mut input = match io.get_params::<2>() {
+        Some(v) => v,
+        None => reject(SyscallError::InvalidParameter as u16).await,
+    };
+
+    // Read length, and move input[0] by one byte
+    let length = usize::from_le_bytes(input[0].read().await);
+
+    // The message is an IntentMessage<PersonalMessage>: the intent, then the message as a BCS
+    // vector<u8>, and nothing else may follow it
+    let (message_start, message_length, display) = {
+        let mut msg = input[0].clone();
+        NoinlineFut(async move {
+            check_intent(&mut msg, IntentScope::PersonalMessage).await;
+            let message_length =
+                <DefaultInterp as AsyncParser<ULEB128, _>>::parse(&DefaultInterp, &mut msg).await
+                    as usize;
+            if INTENT_LENGTH + uleb128_length(message_length) + message_length != length {
+                reject::<()>(SyscallError::InvalidParameter as u16).await;
+            }
+            let message_start = msg.clone();
+            let mut printable = true;
+            for _ in 0..message_length {
+                let [b]: [u8; 1] = msg.read().await;
+                printable &= is_printable(b);
+            }
+            let display = if printable && message_length <= PERSONAL_MESSAGE_TEXT_LENGTH {
+                MessageDisplay::Text
+            } else if message_length <= PERSONAL_MESSAGE_HEX_LENGTH {
+                MessageDisplay::Hex
+            } else {
+                MessageDisplay::Hash
+            };
+            (message_start, message_length, display)
+        })
+        .await
+    };
+
+    let signer = parse_signer(input[1].clone()).await;
+    let hash = NoinlineFut(hash_input(input[0].clone(), length)).await;
+
+    if scroller("Sign", |w| Ok(write!(w, "Personal Message")?)).is_none() {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    }
+    show_signer(&signer).await;
+
+    {
+        let mut msg = message_start;
+        NoinlineFut(async move {
+            let shown: Option<()> = match display {
+                MessageDisplay::Hash => {
+                    scroller_paginated("Message Hash", |w| Ok(write!(w, "0x{hash}")?))
+                }
+                _ => {
+                    try {
+                        let chunks = core::cmp::max(
+                            1,
+                            (message_length + PERSONAL_MESSAGE_CHUNK_LENGTH - 1)
+                                / PERSONAL_MESSAGE_CHUNK_LENGTH,
+                        );
+                        for index in 0..chunks {
+                            let offset = index * PERSONAL_MESSAGE_CHUNK_LENGTH;
+                            let chunk_length = core::cmp::min(
+                                PERSONAL_MESSAGE_CHUNK_LENGTH,
+                                message_length - offset,
+                            );
+                            let mut chunk: ArrayVec<u8, PERSONAL_MESSAGE_CHUNK_LENGTH> =
+                                ArrayVec::new();
+                            for _ in 0..chunk_length {
+                                let [b]: [u8; 1] = msg.read().await;
+                                chunk.push(b);
+                            }
+                            show_message_chunk(display, &chunk, index, chunks)?;
+                        }
+                    }
+                }
+            };
+            if shown.is_none() {
+                reject::<()>(StatusWords::UserCancelled as u16).await;
+            }
+        })
+        .await
+    };
+
+    if final_accept_prompt(&["Sign Message?"]).is_none() {
+        reject::<()>(StatusWords::UserCancelled as u16).await;
+    };
+
+    NoinlineFut(async move {
         let path = BIP_PATH_PARSER.parse(&mut input[1].clone()).await;
-        if !path.starts_with(&BIP32_PREFIX[0..2]) {
+        if check_path(&path).is_none() {
             reject::<()>(SyscallError::InvalidParameter as u16).await;
         }
-        if let Some(sig) = { eddsa_sign(&path, true, &hash.0).ok() } {
-            io.result_final(&sig.0[0..]).await;
+        if let Some(sig) = sign_digest(&path, &hash.0) {
+            io.result_final(&sig).await;
         } else {
             reject::<()>(SyscallError::Unspecified as u16).await;
         }
@@ -789,6 +2553,63 @@ pub async fn sign_apdu(io: HostIO, settings: Settings) {
     .await
 }
 
+// The settings as flags, followed by what this app can sign: the signature schemes, the
+// TransactionKind variants it reviews, and flags for the other instructions
+async fn get_app_configuration_apdu(io: HostIO, settings: Settings) {
+    let settings = settings.get();
+    let mut flags = 0;
+    if settings.blind_signing() == BlindSigning::Enabled {
+        flags |= 1 << 0;
+    }
+    if settings.blind_signing() == BlindSigning::Once {
+        flags |= 1 << 1;
+    }
+    if settings.reject_sender_mismatch {
+        flags |= 1 << 2;
+    }
+    if settings.expert_mode {
+        flags |= 1 << 3;
+    }
+    let schemes = [
+        SignatureScheme::Ed25519 as u8,
+        SignatureScheme::Secp256k1 as u8,
+        SignatureScheme::Secp256r1 as u8,
+    ];
+    // Only ProgrammableTransaction
+    let transaction_kinds = [0];
+    // Personal messages, serialized signatures, and batches of public keys
+    let instructions = (1 << 0) | (1 << 1) | (1 << 2);
+
+    let mut rv = ArrayVec::<u8, 16>::new();
+    let _ = rv.try_push(flags);
+    let _ = rv.try_push(schemes.len() as u8);
+    let _ = rv.try_extend_from_slice(&schemes);
+    let _ = rv.try_push(transaction_kinds.len() as u8);
+    let _ = rv.try_extend_from_slice(&transaction_kinds);
+    let _ = rv.try_push(instructions);
+    io.result_final(&rv).await;
+}
+
+// The crate version, the Cargo features the app was built with, and the build, for support
+async fn get_version_str_apdu(io: HostIO) {
+    let features = [
+        ("speculos", cfg!(feature = "speculos")),
+        ("extra_debug", cfg!(feature = "extra_debug")),
+    ];
+    let mut rv = ArrayString::<128>::new();
+    let _ = write!(
+        rv,
+        "{} {}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    );
+    for (i, (feature, _)) in features.iter().filter(|(_, enabled)| *enabled).enumerate() {
+        let _ = write!(rv, "{}{}", if i == 0 { " +" } else { "," }, feature);
+    }
+    let _ = write!(rv, " build {}", env!("SUI_BUILD_ID"));
+    io.result_final(rv.as_bytes()).await;
+}
+
 pub type APDUsFuture = impl Future<Output = ()>;
 
 #[inline(never)]
@@ -812,11 +2633,27 @@ pub fn handle_apdu_async(io: HostIO, ins: Ins, settings: Settings) -> APDUsFutur
             Ins::GetPubkey => {
                 NoinlineFut(get_address_apdu(io, false)).await;
             }
+            Ins::GetPubkeys => {
+                NoinlineFut(get_pubkeys_apdu(io)).await;
+            }
             Ins::Sign => {
                 trace!("Handling sign");
-                NoinlineFut(sign_apdu(io, settings)).await;
+                NoinlineFut(sign_apdu(io, settings, false)).await;
+            }
+            Ins::SignSerialized => {
+                trace!("Handling sign serialized");
+                NoinlineFut(sign_apdu(io, settings, true)).await;
+            }
+            Ins::SignPersonalMessage => {
+                trace!("Handling sign personal message");
+                NoinlineFut(sign_personal_message_apdu(io)).await;
+            }
+            Ins::GetAppConfiguration => {
+                NoinlineFut(get_app_configuration_apdu(io, settings)).await;
+            }
+            Ins::GetVersionStr => {
+                NoinlineFut(get_version_str_apdu(io)).await;
             }
-            Ins::GetVersionStr => {}
             Ins::Exit => ledger_device_sdk::exit_app(0),
         }
     }
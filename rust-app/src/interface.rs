@@ -13,7 +13,11 @@ pub type SignParameters = (IntentMessage<true>, Bip32Key);
 // Sui Types
 pub type IntentMessage<const PROMPT: bool> = (Intent, TransactionData<PROMPT>);
 
-pub struct TransactionData<const PROMPT: bool>;
+// Whether the sender owns the gas coin is only known after the commands that use it, so the prompt
+// parse is given what the check parse found
+pub struct TransactionData<const PROMPT: bool> {
+    pub sender_owns_gas: bool,
+}
 
 pub type TransactionDataV1<const PROMPT: bool> = (
     TransactionKind<PROMPT>,
@@ -22,23 +26,31 @@ pub type TransactionDataV1<const PROMPT: bool> = (
     TransactionExpiration, // expiration
 );
 
-pub struct TransactionKind<const PROMPT: bool>;
+pub struct TransactionKind<const PROMPT: bool> {
+    pub sender_owns_gas: bool,
+}
 
-pub struct ProgrammableTransaction<const PROMPT: bool>;
+pub struct ProgrammableTransaction<const PROMPT: bool> {
+    pub sender_owns_gas: bool,
+}
 
 pub struct CommandSchema;
 pub struct ArgumentSchema;
 pub struct CallArgSchema;
+pub struct MoveCallSchema;
+pub struct IdentifierSchema;
+pub struct PackageSchema;
 
-pub type GasData<const PROMPT: bool> = (
-    Vec<ObjectRef, { usize::MAX }>, // payment
-    SuiAddress,                     // owner
-    Amount,                         // price
-    Amount,                         // budget
-);
+// The payment (Vec<ObjectRef>), owner (SuiAddress), price (Amount) and budget (Amount) for gas
+pub struct GasData<const PROMPT: bool>;
+pub type GasPayment = Vec<ObjectRef, { usize::MAX }>;
 
 pub struct TransactionExpiration;
 pub type EpochId = U64<{ Endianness::Little }>;
+pub type TimestampSeconds = U64LE;
+// The digest of the chain's genesis checkpoint
+pub type ChainIdentifier = SHA3_256_HASH;
+pub type ExpirationNonce = U32<{ Endianness::Little }>;
 
 pub type ObjectRef = (ObjectID, SequenceNumber, ObjectDigest);
 
@@ -65,10 +77,37 @@ pub type Amount = U64LE;
 pub type U64LE = U64<{ Endianness::Little }>;
 pub type U16LE = U16<{ Endianness::Little }>;
 
-pub type Intent = (IntentVersion, IntentScope, AppId);
-pub type IntentVersion = ULEB128;
-pub type IntentScope = ULEB128;
-pub type AppId = ULEB128;
+pub type Intent = (
+    ULEB128, // scope
+    ULEB128, // version
+    ULEB128, // app_id
+);
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+pub enum IntentScope {
+    TransactionData = 0,
+    TransactionEffects = 1,
+    CheckpointSummary = 2,
+    PersonalMessage = 3,
+    SenderSignedTransaction = 4,
+    ProofOfPossession = 5,
+    HeaderDigest = 6,
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+pub enum IntentVersion {
+    V0 = 0,
+}
+
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
+pub enum AppId {
+    Sui = 0,
+    Narwhal = 1,
+    Consensus = 2,
+}
 
 // TODO: confirm if 33 is indeed ok for all uses of SHA3_256_HASH
 #[allow(non_camel_case_types)]
@@ -81,10 +120,38 @@ pub enum Ins {
     VerifyAddress = 1,
     GetPubkey = 2,
     Sign = 3,
+    SignPersonalMessage = 4,
+    SignSerialized = 5,
+    GetAppConfiguration = 6,
+    GetPubkeys = 7,
     GetVersionStr = 0xfe,
     Exit = 0xff,
 }
 
+// Status words for requests that are well formed, but that the app will not sign
+#[repr(u16)]
+#[derive(Debug, Clone, Copy)]
+pub enum SuiStatusWords {
+    BadIntentScope = 0x6a81,
+    BadIntentVersion = 0x6a82,
+    BadAppId = 0x6a83,
+    SenderMismatch = 0x6a84,
+    // Why a transaction can't be clear signed, when blind signing is disabled
+    UnsupportedTransactionVersion = 0x6a90,
+    UnsupportedTransactionKind = 0x6a91,
+    UnsupportedExpiration = 0x6a92,
+    UnsupportedInput = 0x6a93,
+    UnsupportedCommand = 0x6a94,
+    UnsupportedArgument = 0x6a95,
+    UnsupportedType = 0x6a96,
+    UnsupportedTransfer = 0x6a97,
+    TooManyRecipients = 0x6a98,
+    AmountOverflow = 0x6a99,
+    UnusedSplitCoins = 0x6a9a,
+    NothingToReview = 0x6a9b,
+    TooLarge = 0x6a9c,
+}
+
 impl TryFrom<ApduHeader> for Ins {
     type Error = StatusWords;
     fn try_from(m: ApduHeader) -> Result<Ins, Self::Error> {
@@ -38,6 +38,9 @@ pub mod utils;
 #[cfg(target_family = "bolos")]
 pub mod implementation;
 
+#[cfg(target_family = "bolos")]
+pub mod path;
+
 #[cfg(target_family = "bolos")]
 pub mod menu;
 
@@ -23,8 +23,11 @@ pub enum IdleMenu {
 }
 
 pub enum SettingsSubMenu {
-    EnableBlindSigning,
-    DisableBlindSigning,
+    BlindSigning,
+    BlindSignOnce,
+    SenderMismatch,
+    ExpertMode,
+    GasWarning,
     Back,
 }
 
@@ -44,14 +47,12 @@ impl Menu for IdleMenuWithSettings {
             AppMain => self.idle_menu = Exit,
             ShowVersion => self.idle_menu = AppMain,
             Settings(None) => self.idle_menu = ShowVersion,
-            Settings(Some(Back)) => {
-                if self.settings.get() == 1 {
-                    self.idle_menu = Settings(Some(DisableBlindSigning))
-                } else {
-                    self.idle_menu = Settings(Some(EnableBlindSigning))
-                }
-            }
-            Settings(Some(_)) => self.idle_menu = Settings(Some(Back)),
+            Settings(Some(BlindSigning)) => self.idle_menu = Settings(Some(Back)),
+            Settings(Some(BlindSignOnce)) => self.idle_menu = Settings(Some(BlindSigning)),
+            Settings(Some(SenderMismatch)) => self.idle_menu = Settings(Some(BlindSignOnce)),
+            Settings(Some(ExpertMode)) => self.idle_menu = Settings(Some(SenderMismatch)),
+            Settings(Some(GasWarning)) => self.idle_menu = Settings(Some(ExpertMode)),
+            Settings(Some(Back)) => self.idle_menu = Settings(Some(GasWarning)),
             Exit => self.idle_menu = Settings(None),
         };
     }
@@ -62,14 +63,12 @@ impl Menu for IdleMenuWithSettings {
             AppMain => self.idle_menu = ShowVersion,
             ShowVersion => self.idle_menu = Settings(None),
             Settings(None) => self.idle_menu = Exit,
-            Settings(Some(Back)) => {
-                if self.settings.get() == 1 {
-                    self.idle_menu = Settings(Some(DisableBlindSigning))
-                } else {
-                    self.idle_menu = Settings(Some(EnableBlindSigning))
-                }
-            }
-            Settings(Some(_)) => self.idle_menu = Settings(Some(Back)),
+            Settings(Some(BlindSigning)) => self.idle_menu = Settings(Some(BlindSignOnce)),
+            Settings(Some(BlindSignOnce)) => self.idle_menu = Settings(Some(SenderMismatch)),
+            Settings(Some(SenderMismatch)) => self.idle_menu = Settings(Some(ExpertMode)),
+            Settings(Some(ExpertMode)) => self.idle_menu = Settings(Some(GasWarning)),
+            Settings(Some(GasWarning)) => self.idle_menu = Settings(Some(Back)),
+            Settings(Some(Back)) => self.idle_menu = Settings(Some(BlindSigning)),
             Exit => self.idle_menu = AppMain,
         };
     }
@@ -81,21 +80,38 @@ impl Menu for IdleMenuWithSettings {
             AppMain => None,
             ShowVersion => None,
             Settings(None) => {
-                if self.settings.get() == 1 {
-                    self.idle_menu = Settings(Some(DisableBlindSigning))
-                } else {
-                    self.idle_menu = Settings(Some(EnableBlindSigning))
-                };
+                self.idle_menu = Settings(Some(BlindSigning));
+                None
+            }
+            Settings(Some(BlindSigning)) => {
+                self.settings.update(|s| {
+                    s.set_blind_signing(match s.blind_signing() {
+                        BlindSigning::Enabled => BlindSigning::Disabled,
+                        _ => BlindSigning::Enabled,
+                    })
+                });
                 None
             }
-            Settings(Some(EnableBlindSigning)) => {
-                self.settings.set(&1);
-                self.idle_menu = Settings(Some(DisableBlindSigning));
+            Settings(Some(BlindSignOnce)) => {
+                self.settings.update(|s| {
+                    s.set_blind_signing(match s.blind_signing() {
+                        BlindSigning::Once => BlindSigning::Disabled,
+                        _ => BlindSigning::Once,
+                    })
+                });
                 None
             }
-            Settings(Some(DisableBlindSigning)) => {
-                self.settings.set(&0);
-                self.idle_menu = Settings(Some(EnableBlindSigning));
+            Settings(Some(SenderMismatch)) => {
+                self.settings
+                    .update(|s| s.reject_sender_mismatch = !s.reject_sender_mismatch);
+                None
+            }
+            Settings(Some(ExpertMode)) => {
+                self.settings.update(|s| s.expert_mode = !s.expert_mode);
+                None
+            }
+            Settings(Some(GasWarning)) => {
+                self.settings.update(|s| s.next_gas_warning());
                 None
             }
             Settings(Some(Back)) => {
@@ -113,7 +129,11 @@ impl Menu for IdleMenuWithSettings {
             AppMain => (
                 MenuLabelTop::Icon(&APP_ICON),
                 MenuLabelBottom {
-                    text: "Sui",
+                    text: if self.settings.get().blind_signing() == BlindSigning::Once {
+                        "Blind sign armed"
+                    } else {
+                        "Sui"
+                    },
                     bold: true,
                 },
             ),
@@ -131,17 +151,54 @@ impl Menu for IdleMenuWithSettings {
                     bold: true,
                 },
             ),
-            Settings(Some(EnableBlindSigning)) => (
+            Settings(Some(BlindSigning)) => (
                 MenuLabelTop::Text("Blind Signing"),
                 MenuLabelBottom {
-                    text: "Disabled",
+                    text: if self.settings.get().blind_signing() == BlindSigning::Enabled {
+                        "Enabled"
+                    } else {
+                        "Disabled"
+                    },
                     bold: false,
                 },
             ),
-            Settings(Some(DisableBlindSigning)) => (
-                MenuLabelTop::Text("Blind Signing"),
+            Settings(Some(BlindSignOnce)) => (
+                MenuLabelTop::Text("Blind Sign Once"),
+                MenuLabelBottom {
+                    text: if self.settings.get().blind_signing() == BlindSigning::Once {
+                        "Armed"
+                    } else {
+                        "Not armed"
+                    },
+                    bold: false,
+                },
+            ),
+            Settings(Some(SenderMismatch)) => (
+                MenuLabelTop::Text("Sender Mismatch"),
+                MenuLabelBottom {
+                    text: if self.settings.get().reject_sender_mismatch {
+                        "Reject"
+                    } else {
+                        "Warn"
+                    },
+                    bold: false,
+                },
+            ),
+            Settings(Some(ExpertMode)) => (
+                MenuLabelTop::Text("Expert Mode"),
+                MenuLabelBottom {
+                    text: if self.settings.get().expert_mode {
+                        "Enabled"
+                    } else {
+                        "Disabled"
+                    },
+                    bold: false,
+                },
+            ),
+            Settings(Some(GasWarning)) => (
+                MenuLabelTop::Text("Gas Warning"),
                 MenuLabelBottom {
-                    text: "Enabled",
+                    text: self.settings.get().gas_warning_label(),
                     bold: false,
                 },
             ),
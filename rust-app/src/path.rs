@@ -0,0 +1,115 @@
+use crate::utils::*;
+use alamgu_async_block::*;
+use core::fmt::Write;
+use ledger_device_sdk::io::{StatusWords, SyscallError};
+use ledger_prompts_ui::final_accept_prompt;
+
+// The signature schemes Sui accounts can use, with their Sui flag values
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SignatureScheme {
+    Ed25519 = 0,
+    Secp256k1 = 1,
+    Secp256r1 = 2,
+}
+
+const HARDENED: u32 = 0x8000_0000;
+const SUI_COIN_TYPE: u32 = HARDENED | 784;
+
+impl SignatureScheme {
+    // Sui wallets pick the scheme from the purpose of the path
+    pub fn from_path(path: &[u32]) -> Option<Self> {
+        match path {
+            [purpose, SUI_COIN_TYPE, ..] => match *purpose {
+                p if p == HARDENED | 44 => Some(SignatureScheme::Ed25519),
+                p if p == HARDENED | 54 => Some(SignatureScheme::Secp256k1),
+                p if p == HARDENED | 74 => Some(SignatureScheme::Secp256r1),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // Whether a path component is hardened as in the paths Sui wallets derive: all of
+    // m/44'/784'/{account}'/{change}'/{index}' for Ed25519, and only the first three of
+    // m/54'/784'/{account}'/{change}/{index} (or 74') for the ECDSA schemes
+    fn is_standard_component(self, index: usize, component: u32) -> bool {
+        let hardened = component & HARDENED != 0;
+        match index {
+            0..=2 => hardened,
+            3 | 4 => hardened == (self == SignatureScheme::Ed25519),
+            _ => false,
+        }
+    }
+}
+
+// Displays a derivation path as m/44'/784'/0'/0'/0', with a * after each component that is not
+// hardened the way the standard Sui path for its scheme is, or that is past the end of it
+pub struct DerivationPath<'a>(pub &'a [u32]);
+
+impl core::fmt::Display for DerivationPath<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let scheme = SignatureScheme::from_path(self.0);
+        write!(f, "m")?;
+        for (index, component) in self.0.iter().enumerate() {
+            write!(f, "/{}", component & !HARDENED)?;
+            if component & HARDENED != 0 {
+                write!(f, "'")?;
+            }
+            if !scheme.map_or(false, |s| s.is_standard_component(index, *component)) {
+                write!(f, "*")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// How a path the host asks for is treated
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PathShape {
+    // The five levels Sui wallets derive, hardened as they do, used without asking
+    Standard,
+    // Any other path the scheme can derive, used only once the user confirms it
+    Unusual,
+}
+
+// The scheme and shape of a path, or None if it can't be used: it is not for Sui, or is an
+// Ed25519 path with unhardened levels, which SLIP-10 can't derive
+pub fn check_path(path: &[u32]) -> Option<(SignatureScheme, PathShape)> {
+    let scheme = SignatureScheme::from_path(path)?;
+    if scheme == SignatureScheme::Ed25519 && path.iter().any(|c| c & HARDENED == 0) {
+        return None;
+    }
+    let standard = path.len() == 5
+        && path
+            .iter()
+            .enumerate()
+            .all(|(index, c)| scheme.is_standard_component(index, *c));
+    Some((
+        scheme,
+        if standard {
+            PathShape::Standard
+        } else {
+            PathShape::Unusual
+        },
+    ))
+}
+
+// Checks the path of a request before anything else is shown: it is rejected if it can't be used,
+// and an unusual one must be confirmed
+pub async fn confirm_path(path: &[u32]) -> SignatureScheme {
+    let (scheme, shape) = match check_path(path) {
+        Some(v) => v,
+        None => reject(SyscallError::InvalidParameter as u16).await,
+    };
+    if shape == PathShape::Unusual {
+        let confirmed: Option<()> = try {
+            scroller("WARNING", |w| Ok(write!(w, "Unusual derivation path")?))?;
+            scroller_paginated("Path", |w| Ok(write!(w, "{}", DerivationPath(path))?))?;
+            final_accept_prompt(&[])?;
+        };
+        if confirmed.is_none() {
+            reject::<()>(StatusWords::UserCancelled as u16).await;
+        }
+    }
+    scheme
+}
@@ -1,9 +1,100 @@
 use ledger_device_sdk::nvm::*;
 use ledger_device_sdk::NVMData;
 
-// This is necessary to store the object in NVM and not in RAM
+// This is necessary to store the object in NVM and not in RAM. Installing a new version of the app
+// starts again from the defaults, as the NVM data is written anew with the app; settings are not
+// carried over from the one-byte layout of earlier versions.
 #[link_section = ".nvm_data"]
-static mut SETTINGS: NVMData<AtomicStorage<u8>> = NVMData::new(AtomicStorage::new(&0));
+static mut SETTINGS: NVMData<AtomicStorage<SettingsData>> =
+    NVMData::new(AtomicStorage::new(&SettingsData::DEFAULT));
+
+// Bump this when the fields of SettingsData change; settings stored with another version read as
+// the defaults
+const SETTINGS_VERSION: u8 = 1;
+
+// The gas budgets, in MIST, above which the review warns, from the strictest, in the order the menu
+// cycles through them
+pub const GAS_WARNING_THRESHOLDS: [(Option<u64>, &str); 5] = [
+    (Some(100_000_000), "Above 0.1 SUI"),
+    (Some(1_000_000_000), "Above 1 SUI"),
+    (Some(10_000_000_000), "Above 10 SUI"),
+    (Some(100_000_000_000), "Above 100 SUI"),
+    (None, "Off"),
+];
+
+// Above 1 SUI
+const DEFAULT_GAS_WARNING: u8 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BlindSigning {
+    Disabled,
+    Enabled,
+    // Allowed for the next transaction, then disabled again
+    Once,
+}
+
+#[derive(Clone, Copy)]
+#[repr(C)]
+pub struct SettingsData {
+    // SETTINGS_VERSION, for the layout the settings were stored with
+    version: u8,
+    // A BlindSigning; it was a bool before Once was added, so Disabled and Enabled keep its values
+    blind_signing: u8,
+    // Whether a transaction whose sender is not the signing key is rejected, rather than signed
+    // after a warning
+    pub reject_sender_mismatch: bool,
+    // Whether the review also shows the gas price and the coins paying for gas
+    pub expert_mode: bool,
+    // An index into GAS_WARNING_THRESHOLDS
+    pub gas_warning: u8,
+}
+
+impl SettingsData {
+    const DEFAULT: SettingsData = SettingsData {
+        version: SETTINGS_VERSION,
+        blind_signing: 0,
+        reject_sender_mismatch: false,
+        expert_mode: false,
+        gas_warning: DEFAULT_GAS_WARNING,
+    };
+
+    pub fn blind_signing(&self) -> BlindSigning {
+        match self.blind_signing {
+            1 => BlindSigning::Enabled,
+            2 => BlindSigning::Once,
+            _ => BlindSigning::Disabled,
+        }
+    }
+
+    pub fn set_blind_signing(&mut self, mode: BlindSigning) {
+        self.blind_signing = match mode {
+            BlindSigning::Disabled => 0,
+            BlindSigning::Enabled => 1,
+            BlindSigning::Once => 2,
+        };
+    }
+
+    fn gas_warning_index(&self) -> usize {
+        let index = self.gas_warning as usize;
+        if index < GAS_WARNING_THRESHOLDS.len() {
+            index
+        } else {
+            DEFAULT_GAS_WARNING as usize
+        }
+    }
+
+    pub fn gas_warning_threshold(&self) -> Option<u64> {
+        GAS_WARNING_THRESHOLDS[self.gas_warning_index()].0
+    }
+
+    pub fn gas_warning_label(&self) -> &'static str {
+        GAS_WARNING_THRESHOLDS[self.gas_warning_index()].1
+    }
+
+    pub fn next_gas_warning(&mut self) {
+        self.gas_warning = ((self.gas_warning_index() + 1) % GAS_WARNING_THRESHOLDS.len()) as u8;
+    }
+}
 
 #[derive(Clone, Copy)]
 pub struct Settings;
@@ -16,15 +107,29 @@ impl Default for Settings {
 
 impl Settings {
     #[inline(never)]
-    pub fn get(&self) -> u8 {
+    pub fn get(&self) -> SettingsData {
         let settings = unsafe { SETTINGS.get_mut() };
-        return *settings.get_ref();
+        let stored = *settings.get_ref();
+        if stored.version == SETTINGS_VERSION {
+            stored
+        } else {
+            SettingsData::DEFAULT
+        }
     }
 
     // The inline(never) is important. Otherwise weird segmentation faults happen on speculos.
     #[inline(never)]
-    pub fn set(&mut self, v: &u8) {
+    pub fn set(&mut self, v: &SettingsData) {
         let settings = unsafe { SETTINGS.get_mut() };
-        settings.update(v);
+        settings.update(&SettingsData {
+            version: SETTINGS_VERSION,
+            ..*v
+        });
+    }
+
+    pub fn update(&mut self, f: impl FnOnce(&mut SettingsData)) {
+        let mut v = self.get();
+        f(&mut v);
+        self.set(&v)
     }
 }
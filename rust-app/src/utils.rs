@@ -63,3 +63,71 @@ impl<F: Future> Future for NoinlineFut<F> {
         self.project().0.poll(cx)
     }
 }
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Base58 encodes up to 32 bytes, the way Sui shows transaction digests
+pub fn base58_encode(input: &[u8]) -> Option<arrayvec::ArrayString<44>> {
+    // Little endian base 58 digits of the input, each byte adding at most log(256)/log(58) of them
+    let mut digits = [0u8; 44];
+    let mut length = 0;
+    for byte in input {
+        let mut carry = *byte as u32;
+        for digit in digits[..length].iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            *digits.get_mut(length)? = (carry % 58) as u8;
+            length += 1;
+            carry /= 58;
+        }
+    }
+    let mut encoded = arrayvec::ArrayString::new();
+    // Leading zero bytes are kept as leading 1s
+    for _ in input.iter().take_while(|b| **b == 0) {
+        encoded.try_push('1').ok()?;
+    }
+    for digit in digits[..length].iter().rev() {
+        encoded
+            .try_push(char::from(BASE58_ALPHABET[*digit as usize]))
+            .ok()?;
+    }
+    Some(encoded)
+}
+
+// Seconds since the Unix epoch, shown as a UTC date and time
+pub struct UtcTime(pub u64);
+
+impl core::fmt::Display for UtcTime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let (days, seconds) = (self.0 / 86400, self.0 % 86400);
+        // The civil date from a day count, with years starting in March so leap days come last
+        // C.F. https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719468;
+        let era = z / 146097;
+        let day_of_era = z % 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+        write!(
+            f,
+            "{}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+            year,
+            month,
+            day,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        )
+    }
+}
@@ -25,6 +25,23 @@ export type GetPublicKeyResult = {
   address: Uint8Array;
 };
 
+export type SignTransactionSerializedResult = {
+  signature: Uint8Array;
+  digest: Uint8Array;
+};
+
+export type GetAppConfigurationResult = {
+  blindSigningEnabled: boolean;
+  blindSignOnceArmed: boolean;
+  rejectSenderMismatch: boolean;
+  expertMode: boolean;
+  signatureSchemes: number[];
+  transactionKinds: number[];
+  personalMessages: boolean;
+  serializedSignatures: boolean;
+  publicKeyBatches: boolean;
+};
+
 /**
  * Sui API
  *
@@ -33,6 +50,24 @@ export type GetPublicKeyResult = {
  * const sui = new Sui(transport)
  */
 
+function splitPath(path: string): number[] {
+  return path.split("/").filter((element) => element !== "m").map((element) => {
+    const hardened = element.endsWith("'");
+    const index = parseInt(hardened ? element.slice(0, -1) : element, 10);
+    return hardened ? (index + 0x80000000) >>> 0 : index;
+  });
+}
+
+function buildBip32KeyPayload(path: string): Buffer {
+  const paths = splitPath(path);
+  const payload = Buffer.alloc(1 + paths.length * 4);
+  payload[0] = paths.length;
+  paths.forEach((element, index) => {
+    payload.writeUInt32LE(element, 1 + 4 * index);
+  });
+  return payload;
+}
+
 export default class Sui extends Common {
 
   constructor(transport: Transport) {
@@ -55,5 +90,104 @@ app.
     }
     return { publicKey, address };
   }
-}
 
+  /**
+    * Retrieves the public keys and addresses for several BIP32 paths in one request, as when
+    * discovering accounts.
+    *
+    * @param paths - the paths to retrieve, at most 255.
+    */
+  async getPublicKeys(
+    paths: string[],
+  ): Promise<GetPublicKeyResult[]> {
+    const payload = Buffer.concat([Buffer.from([paths.length]), ...paths.map(buildBip32KeyPayload)]);
+    const rv = await this.sendChunks(0x00, 0x07, 0x00, 0x00, [payload]);
+    const keys = [];
+    let offset = 0;
+    while (offset < rv.length) {
+      const publicKey = rv.subarray(offset + 1, offset + 1 + rv[offset]);
+      offset += 1 + publicKey.length;
+      const address = rv.subarray(offset + 1, offset + 1 + rv[offset]);
+      offset += 1 + address.length;
+      keys.push({ publicKey, address });
+    }
+    return keys;
+  }
+
+  /**
+    * Signs a personal message with the key for a particular BIP32 path.
+    *
+    * @param path - the path of the key to sign with.
+    * @param message - the intent message: the personal message intent, followed by the message
+    * as a BCS vector<u8>.
+    */
+  async signPersonalMessage(
+    path: string,
+    message: Uint8Array,
+  ): Promise<SignTransactionResult> {
+    const rawMessage = Buffer.from(message);
+    const length = Buffer.alloc(4);
+    length.writeUInt32LE(rawMessage.length);
+    const signature = await this.sendChunks(0x00, 0x04, 0x00, 0x00, [
+      Buffer.concat([length, rawMessage]),
+      buildBip32KeyPayload(path),
+    ]);
+    return { signature };
+  }
+
+  /**
+    * Signs a transaction like signTransaction, but returns the signature serialized the way Sui
+    * expects it, flag || signature || public key, along with the transaction digest.
+    *
+    * @param path - the path of the key to sign with.
+    * @param txn - the intent message of the transaction.
+    */
+  async signTransactionSerialized(
+    path: string,
+    txn: Uint8Array,
+  ): Promise<SignTransactionSerializedResult> {
+    const rawTxn = Buffer.from(txn);
+    const length = Buffer.alloc(4);
+    length.writeUInt32LE(rawTxn.length);
+    const rv = await this.sendChunks(0x00, 0x05, 0x00, 0x00, [
+      Buffer.concat([length, rawTxn]),
+      buildBip32KeyPayload(path),
+    ]);
+    return {
+      signature: rv.subarray(0, rv.length - 32),
+      digest: rv.subarray(rv.length - 32),
+    };
+  }
+
+  /**
+    * Retrieves the app's settings, and what it can sign.
+    */
+  async getAppConfiguration(): Promise<GetAppConfigurationResult> {
+    const rv = await this.sendChunks(0x00, 0x06, 0x00, 0x00, Buffer.alloc(1));
+    const flags = rv[0];
+    const schemeCount = rv[1];
+    const signatureSchemes = Array.from(rv.subarray(2, 2 + schemeCount));
+    const kindCount = rv[2 + schemeCount];
+    const transactionKinds = Array.from(rv.subarray(3 + schemeCount, 3 + schemeCount + kindCount));
+    const instructions = rv[3 + schemeCount + kindCount];
+    return {
+      blindSigningEnabled: (flags & 0x01) != 0,
+      blindSignOnceArmed: (flags & 0x02) != 0,
+      rejectSenderMismatch: (flags & 0x04) != 0,
+      expertMode: (flags & 0x08) != 0,
+      signatureSchemes,
+      transactionKinds,
+      personalMessages: (instructions & 0x01) != 0,
+      serializedSignatures: (instructions & 0x02) != 0,
+      publicKeyBatches: (instructions & 0x04) != 0,
+    };
+  }
+
+  /**
+    * Retrieves the app version as a string, with the features it was built with and its build.
+    */
+  async getVersionString(): Promise<string> {
+    const rv = await this.sendChunks(0x00, 0xfe, 0x00, 0x00, Buffer.alloc(1));
+    return Buffer.from(rv).toString("utf8");
+  }
+}
@@ -173,7 +173,8 @@ const sendCommandAndAccept = async function(command : any, prompts : any[]) {
   }
 }
 
-const sendCommandExpectFail = async function(command : any) {
+// With statusCode given, the command must fail with that status word
+const sendCommandExpectFail = async function(command : any, statusCode? : number) {
   await setAcceptAutomationRules();
   await Axios.delete(BASE_URL + "/events");
 
@@ -182,20 +183,68 @@ const sendCommandExpectFail = async function(command : any) {
   // client.sendChunks = client.sendWithBlocks; // Use Block protocol
 
   try { await command(client); } catch(e) {
+    if (statusCode !== undefined) expect(e.statusCode).to.equal(statusCode);
     return;
   }
   expect.fail("Command should have failed");
 }
 
+// The confirmation the app asks for before using a path that is not the standard Sui one,
+// 44'/784'/a'/c'/i' or 54'/784'/a'/c/i (74' for secp256r1), as most tests use 44'/784'/0'
+const pathPrompts = function(path : string) : any[] {
+  if (/^(44'\/784'\/\d+'\/\d+'\/\d+'|[57]4'\/784'\/\d+'\/\d+\/\d+)$/.test(path)) return [];
+  return [
+    {
+      "header": "WARNING",
+      "prompt": "Unusual derivation path"
+    },
+    {
+      "header": "Path",
+      "prompt": "m/" + path,
+      "paginate": true
+    },
+    {
+      "text": "Confirm",
+      "x": 43,
+      "y": 11,
+    },
+  ];
+}
+
+// Toggles the setting at the given position in the settings menu, and returns to the main screen
+let toggleSetting = async function(position : number) {
+  const press = async (button : string) => Axios.post(BASE_URL + "/button/" + button, {"action":"press-and-release"});
+  await press("right");
+  await press("right");
+  await press("both");
+  for (let i = 0; i < position; i++) await press("right");
+  await press("both");
+  // Back is the last entry, just left of the first
+  for (let i = 0; i <= position; i++) await press("left");
+  await press("both");
+  await press("left");
+  await press("left");
+}
+
 let toggleBlindSigningSettings = async function() {
-  await Axios.post(BASE_URL + "/button/right", {"action":"press-and-release"});
-  await Axios.post(BASE_URL + "/button/right", {"action":"press-and-release"});
-  await Axios.post(BASE_URL + "/button/both", {"action":"press-and-release"});
-  await Axios.post(BASE_URL + "/button/both", {"action":"press-and-release"});
-  await Axios.post(BASE_URL + "/button/right", {"action":"press-and-release"});
-  await Axios.post(BASE_URL + "/button/both", {"action":"press-and-release"});
-  await Axios.post(BASE_URL + "/button/left", {"action":"press-and-release"});
-  await Axios.post(BASE_URL + "/button/left", {"action":"press-and-release"});
+  await toggleSetting(0);
+}
+
+let toggleBlindSignOnceSettings = async function() {
+  await toggleSetting(1);
+}
+
+let toggleSenderMismatchSettings = async function() {
+  await toggleSetting(2);
+}
+
+let toggleExpertModeSettings = async function() {
+  await toggleSetting(3);
+}
+
+// Moves the Gas Warning setting on to the next threshold
+let toggleGasWarningSettings = async function() {
+  await toggleSetting(4);
 }
 
-export { sendCommandAndAccept, BASE_URL, sendCommandExpectFail, toggleBlindSigningSettings }
+export { sendCommandAndAccept, BASE_URL, sendCommandExpectFail, pathPrompts, toggleBlindSigningSettings, toggleBlindSignOnceSettings, toggleSenderMismatchSettings, toggleExpertModeSettings, toggleGasWarningSettings }
@@ -1,4 +1,4 @@
-import { VERSION, sendCommandAndAccept } from "./common";
+import { VERSION, sendCommandAndAccept, toggleBlindSigningSettings } from "./common";
 import { expect } from 'chai';
 import { describe, it } from 'mocha';
 
@@ -11,4 +11,34 @@ describe("get version tests", function() {
       expect(rv.patch).to.equal(VERSION.patch);
       }, []);
     });
+
+  it("can get the app version string", async () => {
+    await sendCommandAndAccept(async (client : any) => {
+      var rv = await client.getVersionString();
+      expect(rv).to.match(new RegExp(`^sui ${VERSION.major}\\.${VERSION.minor}\\.${VERSION.patch}( \\+[a-z_,]+)? build \\S+$`));
+      }, []);
+    });
+
+  it("can get the app configuration", async () => {
+    await sendCommandAndAccept(async (client : any) => {
+      var rv = await client.getAppConfiguration();
+      expect(rv.blindSigningEnabled).to.equal(false);
+      expect(rv.blindSignOnceArmed).to.equal(false);
+      expect(rv.signatureSchemes).to.deep.equal([0, 1, 2]);
+      expect(rv.transactionKinds).to.deep.equal([0]);
+      expect(rv.personalMessages).to.equal(true);
+      expect(rv.serializedSignatures).to.equal(true);
+      expect(rv.publicKeyBatches).to.equal(true);
+      }, []);
+    });
+
+  it("reports blind signing in the app configuration", async () => {
+    await toggleBlindSigningSettings();
+    await sendCommandAndAccept(async (client : any) => {
+      var rv = await client.getAppConfiguration();
+      expect(rv.blindSigningEnabled).to.equal(true);
+      }, []);
+    // reset back to disabled
+    await toggleBlindSigningSettings();
+    });
 });
@@ -1,8 +1,9 @@
-import { sendCommandAndAccept, BASE_URL, sendCommandExpectFail, toggleBlindSigningSettings } from "./common";
+import { sendCommandAndAccept, BASE_URL, sendCommandExpectFail, toggleBlindSigningSettings, pathPrompts } from "./common";
 import { expect } from 'chai';
 import { describe, it } from 'mocha';
 import Axios from 'axios';
 import type Sui from "./Sui";
+import * as blake2b from "blake2b";
 
 describe('public key tests', () => {
 
@@ -18,7 +19,7 @@ describe('public key tests', () => {
       expect(new Buffer(rv.publicKey).toString('hex')).to.equal("6fc6f39448ad7af0953b78b16d0f840e6fe718ba4a89384239ff20ed088da2fa");
       expect(new Buffer(rv.address).toString('hex')).to.equal("56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5");
       return;
-    }, []);
+    }, pathPrompts("44'/784'/0'"));
   });
 
   it('does address verification', async () => {
@@ -29,10 +30,16 @@ describe('public key tests', () => {
       expect(new Buffer(rv.address).toString('hex')).to.equal("56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5");
       return;
     }, [
+      ...pathPrompts("44'/784'/0'"),
       {
         "header": "Provide Public Key",
         "prompt": "",
       },
+      {
+        "header": "Path",
+        "prompt": "m/44'/784'/0'",
+        "paginate": true,
+      },
       {
         "header": "Address",
         "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
@@ -45,4 +52,73 @@ describe('public key tests', () => {
       },
     ]);
   });
+
+  for (const [scheme, flag, path] of [["secp256k1", 1, "54'/784'/0'/0/0"], ["secp256r1", 2, "74'/784'/0'/0/0"]] as const) {
+    it(`provides a ${scheme} public key`, async () => {
+
+      await sendCommandAndAccept(async (client : Sui) => {
+        const rv = await client.getPublicKey(path);
+        expect(rv.publicKey.length).to.equal(33);
+        expect([2, 3]).to.include(rv.publicKey[0]);
+        const address = blake2b(32).update(Buffer.from([flag])).update(Buffer.from(rv.publicKey)).digest();
+        expect(new Buffer(rv.address).toString('hex')).to.equal(Buffer.from(address).toString('hex'));
+        return;
+      }, []);
+    });
+  }
+
+  it('provides a public key for the standard path without asking', async () => {
+
+    await sendCommandAndAccept(async (client : Sui) => {
+      const rv = await client.getPublicKey("44'/784'/0'/0'/0'");
+      expect(rv.publicKey.length).to.equal(32);
+      return;
+    }, []);
+  });
+
+  it('asks to confirm an unusual path, marking the unusual components', async () => {
+
+    await sendCommandAndAccept(async (client : Sui) => {
+      const rv = await client.getPublicKey("54'/784'/0'/0'/0");
+      expect(rv.publicKey.length).to.equal(33);
+      return;
+    }, [
+      {
+        "header": "WARNING",
+        "prompt": "Unusual derivation path",
+      },
+      {
+        "header": "Path",
+        "prompt": "m/54'/784'/0'/0'*/0",
+        "paginate": true,
+      },
+      {
+        "text": "Confirm",
+        "x": 43,
+        "y": 11,
+      },
+    ]);
+  });
+
+  it('provides the public keys of several accounts in one request', async () => {
+
+    const paths = [0, 1, 2].map(account => `44'/784'/${account}'/0'/0'`);
+    await sendCommandAndAccept(async (client : Sui) => {
+      const keys = await client.getPublicKeys(paths);
+      expect(keys.length).to.equal(paths.length);
+      for (let i = 0; i < paths.length; i++) {
+        const single = await client.getPublicKey(paths[i]);
+        expect(Buffer.from(keys[i].publicKey).toString('hex')).to.equal(Buffer.from(single.publicKey).toString('hex'));
+        expect(Buffer.from(keys[i].address).toString('hex')).to.equal(Buffer.from(single.address).toString('hex'));
+      }
+      return;
+    }, []);
+  });
+
+  it('rejects an Ed25519 path with unhardened levels', async () => {
+
+    await sendCommandExpectFail(async (client : Sui) => {
+      await client.getPublicKey("44'/784'/0'/0/0");
+    });
+  });
 });
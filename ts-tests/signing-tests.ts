@@ -1,10 +1,11 @@
-import { VERSION, sendCommandAndAccept, BASE_URL, sendCommandExpectFail, toggleBlindSigningSettings } from "./common";
+import { VERSION, sendCommandAndAccept, BASE_URL, sendCommandExpectFail, pathPrompts, toggleBlindSigningSettings, toggleBlindSignOnceSettings, toggleSenderMismatchSettings, toggleExpertModeSettings, toggleGasWarningSettings } from "./common";
 import { expect } from 'chai';
 import { describe, it } from 'mocha';
 import Axios from 'axios';
 import type Sui from "./Sui";
 import * as blake2b from "blake2b";
 import { instantiate, Nacl } from "js-nacl";
+import * as crypto from "crypto";
 
 let nacl : Nacl =null;
 
@@ -27,7 +28,7 @@ function testTransaction(path: string, txn: Buffer, prompts: any[]) {
           publicKey,
       );
       expect(pass).to.equal(true);
-    }, prompts);
+    }, [...pathPrompts(path), ...prompts]);
   }
 }
 
@@ -50,6 +51,1176 @@ describe("Signing tests", function() {
            "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
            "paginate": true
          },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Sender",
+           "prompt": "0x6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21",
+           "paginate": true
+         },
+         {
+           "header": "WARNING",
+           "prompt": "Sender is not the signing account"
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "SUI 0.001"
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a transaction sending the entire balance",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("00000000000200204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b01009f3b1c5a2e6d4c8b7a0f1e2d3c4b5a69788796a5b4c3d2e1f0a9b8c7d6e5f4a303000000000000002022222222222222222222222222222222222222222222222222222222222222220203000101010001010001000056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e80300000000000080841e000000000000", "hex"),
+       [
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "Entire balance (minus gas)",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.002"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  // The gas coin is the sponsor's, so the sender's coins merged into it are shown, and what is sent
+  // is not the sender's balance
+  it("shows coins merged into a sponsor's gas coin",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("00000000000200204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b01009f3b1c5a2e6d4c8b7a0f1e2d3c4b5a69788796a5b4c3d2e1f0a9b8c7d6e5f4a30300000000000000202222222222222222222222222222222222222222222222222222222222222222020300010101000101000100006fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e210112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e80300000000000080841e000000000000", "hex"),
+       [
+         {
+           "header": "Review",
+           "prompt": "Transaction"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Sender",
+           "prompt": "0x6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21",
+           "paginate": true
+         },
+         {
+           "header": "WARNING",
+           "prompt": "Sender is not the signing account"
+         },
+         {
+           "header": "Merge Coins",
+           "prompt": "1"
+         },
+         {
+           "header": "Into",
+           "prompt": "Gas coin",
+           "paginate": true
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "Gas owner's coin (minus gas)",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.002"
+         },
+         {
+           "header": "Gas paid by",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a sponsored transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b020200010100000101030000000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c473307000000000000002022222222222222222222222222222222222222222222222222222222222222226fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21e8030000000000000c0400000000000000", "hex"),
+       [
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "SUI 0.001"
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "header": "Gas paid by",
+           "prompt": "0x6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21",
+           "paginate": true
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign as the gas sponsor of another account's transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("00000000000200204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b0100333333333333333333333333333333333333333333333333333333333333333301000000000000002044444444444444444444444444444444444444444444444444444444444444440101010101000100006fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e210112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c0400000000000000", "hex"),
+       [
+         {
+           "header": "Sponsor",
+           "prompt": "Transaction"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Sponsoring",
+           "prompt": "0x6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "header": "Gas Price",
+           "prompt": "1000 MIST"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  // The gas coin of a sponsored transaction is the sponsor's, so spending it is reviewed in full
+  it("shows the full review to a gas sponsor whose gas coin is spent",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b02020001010000010103000000000101006fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e210112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c0400000000000000", "hex"),
+       [
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Sender",
+           "prompt": "0x6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21",
+           "paginate": true
+         },
+         {
+           "header": "WARNING",
+           "prompt": "Sender is not the signing account"
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "SUI 0.001"
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "header": "Gas paid by",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a transaction that expires after an epoch",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b020200010100000101030000000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c04000000000000010002000000000000", "hex"),
+       [
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "SUI 0.001"
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "header": "Expires",
+           "prompt": "After epoch 512"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a transaction valid during a range of epochs and times",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b020200010100000101030000000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c040000000000000201ff01000000000000010002000000000000000180857467000000002035834a8ac17ca48fb14ac8f99c17c98747e95dd07294ae41a46b382246a4499b07000000", "hex"),
+       [
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "SUI 0.001"
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "header": "Valid From",
+           "prompt": "Epoch 511"
+         },
+         {
+           "header": "Expires",
+           "prompt": "After epoch 512"
+         },
+         {
+           "header": "Valid Until",
+           "prompt": "2025-01-01 00:00:00 UTC"
+         },
+         {
+           "header": "Chain",
+           "prompt": "35834a8a"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("shows the gas price and payment in expert mode", async function () {
+    await toggleExpertModeSettings();
+    await Axios.delete(BASE_URL + "/events");
+    await testTransaction(
+      "44'/784'/0'",
+      Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b020200010100000101030000000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50212a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c473307000000000000002022222222222222222222222222222222222222222222222222222222222222229f3b1c5a2e6d4c8b7a0f1e2d3c4b5a69788796a5b4c3d2e1f0a9b8c7d6e5f4a3030000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c0400000000000000", "hex"),
+      [
+        {
+          "header": "Transfer",
+          "prompt": "SUI"
+        },
+        {
+          "header": "From",
+          "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+          "paginate": true
+        },
+        {
+          "header": "Path",
+          "prompt": "m/44'/784'/0'",
+          "paginate": true
+        },
+        {
+          "header": "To",
+          "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+          "paginate": true
+        },
+        {
+          "header": "Amount",
+          "prompt": "SUI 0.001"
+        },
+        {
+          "header": "Max Gas",
+          "prompt": "SUI 0.000001036"
+        },
+        {
+          "header": "Gas Price",
+          "prompt": "1000 MIST"
+        },
+        {
+          "header": "Gas Coins",
+          "prompt": "2"
+        },
+        {
+          "header": "Gas Coin 1",
+          "prompt": "0x12a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733",
+          "paginate": true
+        },
+        {
+          "header": "Gas Coin 2",
+          "prompt": "0x9f3b1c5a2e6d4c8b7a0f1e2d3c4b5a69788796a5b4c3d2e1f0a9b8c7d6e5f4a3",
+          "paginate": true
+        },
+        {
+          "text": "Sign Transaction?",
+          "x": 19,
+          "y": 11
+        },
+        {
+          "text": "Confirm",
+          "x": 43,
+          "y": 11,
+        }
+      ])();
+    await Axios.delete(BASE_URL + "/events");
+    // reset back to disabled
+    await toggleExpertModeSettings();
+  });
+
+  it("warns about a gas budget over the threshold",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b020200010100000101030000000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e803000000000000009435770000000000", "hex"),
+       [
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "SUI 0.001"
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 2.0"
+         },
+         {
+           "header": "WARNING",
+           "prompt": "High gas budget!"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("doesn't warn about a gas budget under a raised threshold", async function () {
+    // From 1 SUI to 10 SUI
+    await toggleGasWarningSettings();
+    await Axios.delete(BASE_URL + "/events");
+    await testTransaction(
+      "44'/784'/0'",
+      Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b020200010100000101030000000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e803000000000000009435770000000000", "hex"),
+      [
+        {
+          "header": "Transfer",
+          "prompt": "SUI"
+        },
+        {
+          "header": "From",
+          "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+          "paginate": true
+        },
+        {
+          "header": "Path",
+          "prompt": "m/44'/784'/0'",
+          "paginate": true
+        },
+        {
+          "header": "To",
+          "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+          "paginate": true
+        },
+        {
+          "header": "Amount",
+          "prompt": "SUI 0.001"
+        },
+        {
+          "header": "Max Gas",
+          "prompt": "SUI 2.0"
+        },
+        {
+          "text": "Sign Transaction?",
+          "x": 19,
+          "y": 11
+        },
+        {
+          "text": "Confirm",
+          "x": 43,
+          "y": 11,
+        }
+      ]
+    )();
+    // back round through 100 SUI, Off and 0.1 SUI to the default of 1 SUI
+    for (let i = 0; i < 4; i++) {
+      await toggleGasWarningSettings();
+    }
+  });
+
+  it("can sign a transaction paying several recipients",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("000000000004000840420f00000000000008a02526000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b00206fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e2103020002010000010100010103000000000102000101030000010001030056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e80300000000000080841e000000000000", "hex"),
+       [
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "To 1",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount 1",
+           "prompt": "SUI 0.001"
+         },
+         {
+           "header": "To 2",
+           "prompt": "0x6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21",
+           "paginate": true
+         },
+         {
+           "header": "Amount 2",
+           "prompt": "SUI 0.0025"
+         },
+         {
+           "header": "Total Amount",
+           "prompt": "SUI 0.0035"
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.002"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a stake transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("00000000000301010000000000000000000000000000000000000000000000000000000000000005010000000000000001000800ca9a3b00000000002044b1b319e23495995fc837dafd28fc6af8b645edddff0fc1467f1ad631362c23020200010101000000000000000000000000000000000000000000000000000000000000000000030a7375695f73797374656d11726571756573745f6164645f7374616b65000301000002000001020056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e80300000000000080841e000000000000", "hex"),
+       [
+         {
+           "header": "Stake",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Stake Amount",
+           "prompt": "SUI 1.0"
+         },
+         {
+           "header": "Validator",
+           "prompt": "0x44b1b319e23495995fc837dafd28fc6af8b645edddff0fc1467f1ad631362c23",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.002"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a withdraw stake transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("0000000000020101000000000000000000000000000000000000000000000000000000000000000501000000000000000101008b6e0ba6f5e4b4b9c1cbd6f3b8c4a4d4e2f1b7a6c5d4e3f2a1b0c9d8e7f6a5b40c00000000000000202222222222222222222222222222222222222222222222222222222222222222010000000000000000000000000000000000000000000000000000000000000000030a7375695f73797374656d16726571756573745f77697468647261775f7374616b65000201000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e80300000000000080841e000000000000", "hex"),
+       [
+         {
+           "header": "Withdraw",
+           "prompt": "Stake"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Staked SUI",
+           "prompt": "0x8b6e0ba6f5e4b4b9c1cbd6f3b8c4a4d4e2f1b7a6c5d4e3f2a1b0c9d8e7f6a5b4",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.002"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a move call transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("00000000000201009f3bfe1a7cdd6a4e5da8b55e2cd3b1d96a7ad5e0b0a9e35d6f3f2ff66b4ea1c003000000000000002022222222222222222222222222222222222222222222222222222222222222220008e80300000000000001000000000000000000000000000000000000000000000000000000000000000002037061790573706c6974010700000000000000000000000000000000000000000000000000000000000000020373756903535549000201000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e80300000000000080841e000000000000", "hex"),
+       [
+         {
+           "header": "Review",
+           "prompt": "Transaction"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Move Call",
+           "prompt": "0x2::pay::split",
+           "paginate": true
+         },
+         {
+           "header": "Type Arguments",
+           "prompt": "0x2::sui::SUI",
+           "paginate": true
+         },
+         {
+           "header": "Argument 1",
+           "prompt": "Object 0x9f3bfe1a7cdd6a4e5da8b55e2cd3b1d96a7ad5e0b0a9e35d6f3f2ff66b4ea1c0",
+           "paginate": true
+         },
+         {
+           "header": "Argument 2",
+           "prompt": "Pure 0xe803000000000000",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.002"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a publish transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("000000000001002056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50204011ca11ceb0b060000000601000203020a050c0607120a081c200c3c07000200000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000002010102000001000056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e80300000000000080f0fa020000000000", "hex"),
+       [
+         {
+           "header": "Review",
+           "prompt": "Transaction"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Publish",
+           "prompt": "Package"
+         },
+         {
+           "header": "Modules",
+           "prompt": "1"
+         },
+         {
+           "header": "Package Digest",
+           "prompt": "0x16b7d1147a4197bf40cc5bd30f54c87d07fca9afe900189df7271ed7bc22c260",
+           "paginate": true
+         },
+         {
+           "header": "Transfer Objects",
+           "prompt": "1"
+         },
+         {
+           "header": "Object 1",
+           "prompt": "Result 0",
+           "paginate": true
+         },
+         {
+           "header": "To",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.05"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a merge coins transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("0000000000030100a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a103000000000000002022222222222222222222222222222222222222222222222222222222222222220100b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b203000000000000002022222222222222222222222222222222222222222222222222222222222222220100c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3030000000000000020222222222222222222222222222222222222222222222222222222222222222201030100000201010001020056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c0400000000000000", "hex"),
+       [
+         {
+           "header": "Review",
+           "prompt": "Transaction"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Merge Coins",
+           "prompt": "2"
+         },
+         {
+           "header": "Into",
+           "prompt": "Object 0xa1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1a1",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a make move vec transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("0000000000020100b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b2b203000000000000002022222222222222222222222222222222222222222222222222222222222222220100c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3c3030000000000000020222222222222222222222222222222222222222222222222222222222222222201050107000000000000000000000000000000000000000000000000000000000000000204636f696e04436f696e010700000000000000000000000000000000000000000000000000000000000000020373756903535549000201000001010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c0400000000000000", "hex"),
+       [
+         {
+           "header": "Review",
+           "prompt": "Transaction"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Make Vector",
+           "prompt": "2"
+         },
+         {
+           "header": "Element Type",
+           "prompt": "0x2::coin::Coin<0x2::sui::SUI>",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  // The policy comes from the authorize_upgrade call that made the ticket
+  it("can sign an upgrade transaction",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("000000000003010033333333333333333333333333333333333333333333333333333333333333330300000000000000202222222222222222222222222222222222222222222222222222222222222222000100002120000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f03000000000000000000000000000000000000000000000000000000000000000002077061636b61676511617574686f72697a655f75706772616465000301000001010001020006011ca11ceb0b060000000601000203020a050c0607120a081c200c3c070002000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000024444444444444444444444444444444444444444444444444444444444444444020000000000000000000000000000000000000000000000000000000000000000000002077061636b6167650e636f6d6d69745f75706772616465000201000002010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c0400000000000000", "hex"),
+       [
+         {
+           "header": "Review",
+           "prompt": "Transaction"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Move Call",
+           "prompt": "0x2::package::authorize_upgrade",
+           "paginate": true
+         },
+         {
+           "header": "Argument 1",
+           "prompt": "Object 0x3333333333333333333333333333333333333333333333333333333333333333",
+           "paginate": true
+         },
+         {
+           "header": "Argument 2",
+           "prompt": "Pure 0x00",
+           "paginate": true
+         },
+         {
+           "header": "Argument 3",
+           "prompt": "Pure (33 bytes) hash 0xa0979ea5ba76abcd628615ead2f9085e4ed73ed9190bf622e653ef2e8d3cdfd8 33",
+           "paginate": true
+         },
+         {
+           "header": "Upgrade",
+           "prompt": "0x4444444444444444444444444444444444444444444444444444444444444444",
+           "paginate": true
+         },
+         {
+           "header": "Modules",
+           "prompt": "1"
+         },
+         {
+           "header": "Package Digest",
+           "prompt": "0x16b7d1147a4197bf40cc5bd30f54c87d07fca9afe900189df7271ed7bc22c260",
+           "paginate": true
+         },
+         {
+           "header": "Upgrade Ticket",
+           "prompt": "Result 0",
+           "paginate": true
+         },
+         {
+           "header": "Policy",
+           "prompt": "Compatible"
+         },
+         {
+           "header": "Move Call",
+           "prompt": "0x2::package::commit_upgrade",
+           "paginate": true
+         },
+         {
+           "header": "Argument 1",
+           "prompt": "Object 0x3333333333333333333333333333333333333333333333333333333333333333",
+           "paginate": true
+         },
+         {
+           "header": "Argument 2",
+           "prompt": "Result 1",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  // A ticket from a package's own policy can't be traced to a policy the app knows
+  it("can sign an upgrade transaction with a custom upgrade policy",
+     testTransaction(
+       "44'/784'/0'",
+       Buffer.from("00000000000101003333333333333333333333333333333333333333333333333333333333333333030000000000000020222222222222222222222222222222222222222222222222222222222222222203000000000000000000000000000000000000000000000000000000000000000abc03676f7609617574686f72697a65000101000006011ca11ceb0b060000000601000203020a050c0607120a081c200c3c070002000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000024444444444444444444444444444444444444444444444444444444444444444020000000000000000000000000000000000000000000000000000000000000000000abc03676f7606636f6d6d6974000201000002010056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e8030000000000000c0400000000000000", "hex"),
+       [
+         {
+           "header": "Review",
+           "prompt": "Transaction"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Move Call",
+           "prompt": "0xabc::gov::authorize",
+           "paginate": true
+         },
+         {
+           "header": "Argument 1",
+           "prompt": "Object 0x3333333333333333333333333333333333333333333333333333333333333333",
+           "paginate": true
+         },
+         {
+           "header": "Upgrade",
+           "prompt": "0x4444444444444444444444444444444444444444444444444444444444444444",
+           "paginate": true
+         },
+         {
+           "header": "Modules",
+           "prompt": "1"
+         },
+         {
+           "header": "Package Digest",
+           "prompt": "0x16b7d1147a4197bf40cc5bd30f54c87d07fca9afe900189df7271ed7bc22c260",
+           "paginate": true
+         },
+         {
+           "header": "Upgrade Ticket",
+           "prompt": "Result 0",
+           "paginate": true
+         },
+         {
+           "header": "Policy",
+           "prompt": "Unknown"
+         },
+         {
+           "header": "Move Call",
+           "prompt": "0xabc::gov::commit",
+           "paginate": true
+         },
+         {
+           "header": "Argument 1",
+           "prompt": "Object 0x3333333333333333333333333333333333333333333333333333333333333333",
+           "paginate": true
+         },
+         {
+           "header": "Argument 2",
+           "prompt": "Result 1",
+           "paginate": true
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  // DER SubjectPublicKeyInfo prefixes for compressed secp256k1 and secp256r1 points
+  for (const [scheme, path, spkiPrefix] of [
+    ["secp256k1", "54'/784'/0'/0/0", "3036301006072a8648ce3d020106052b8104000a032200"],
+    ["secp256r1", "74'/784'/0'/0/0", "3039301306072a8648ce3d020106082a8648ce3d030107032200"],
+  ] as const) {
+    it(`can sign a transaction with a ${scheme} key`, async function () {
+      // The transfer from the original test, sent and paid for by this key's account
+      const txnTemplate = "000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b0202000101000001010200000101006fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e210112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020f2fd3c87b227f1015182fe4348ed680d7ed32bcd3269704252c03e1d0b13d30d6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e2101000000000000000c0400000000000000";
+      let publicKey : Uint8Array = null;
+      let address : Uint8Array = null;
+      await sendCommandAndAccept(async (client : Sui) => {
+        ({ publicKey, address } = await client.getPublicKey(path));
+      }, []);
+      const txn = Buffer.from(txnTemplate.split("6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21").join(Buffer.from(address).toString("hex")), "hex");
+
+      await sendCommandAndAccept(async (client : Sui) => {
+        const sig = await client.signTransaction(path, txn);
+        expect(sig.signature.length).to.equal(64);
+        const key = crypto.createPublicKey({
+          key: Buffer.concat([Buffer.from(spkiPrefix, "hex"), Buffer.from(publicKey)]),
+          format: "der",
+          type: "spki",
+        });
+        const pass = crypto.verify(
+          "sha256",
+          blake2b(32).update(txn).digest(),
+          { key, dsaEncoding: "ieee-p1363" },
+          sig.signature,
+        );
+        expect(pass).to.equal(true);
+      }, [
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x" + Buffer.from(address).toString("hex"),
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/" + path,
+           "paginate": true
+         },
          {
            "header": "To",
            "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
@@ -73,8 +1244,82 @@ describe("Signing tests", function() {
            "x": 43,
            "y": 11,
          }
-       ]
-     ));
+      ]);
+    });
+  }
+
+  it("can sign a transaction returning the serialized signature", async function () {
+    const path = "44'/784'/0'";
+    const txn = Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b0202000101000001010200000101006fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e210112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020f2fd3c87b227f1015182fe4348ed680d7ed32bcd3269704252c03e1d0b13d30d6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e2101000000000000000c0400000000000000", "hex");
+
+    await sendCommandAndAccept(async (client : Sui) => {
+      const { publicKey } = await client.getPublicKey(path);
+
+      // We don't want the prompts from getPublicKey in our result
+      await Axios.delete(BASE_URL + "/events");
+
+      const { signature, digest } = await client.signTransactionSerialized(path, txn);
+      expect(signature.length).to.equal(1 + 64 + 32);
+      expect(signature[0]).to.equal(0);
+      expect(Buffer.from(signature.subarray(65)).toString("hex")).to.equal(Buffer.from(publicKey).toString("hex"));
+      const pass = nacl.crypto_sign_verify_detached(
+          signature.subarray(1, 65),
+          blake2b(32).update(txn).digest(),
+          publicKey,
+      );
+      expect(pass).to.equal(true);
+      const expectedDigest = blake2b(32).update(Buffer.from("TransactionData::")).update(txn.subarray(3)).digest();
+      expect(Buffer.from(digest).toString("hex")).to.equal(Buffer.from(expectedDigest).toString("hex"));
+    }, [
+         ...pathPrompts(path),
+         {
+           "header": "Transfer",
+           "prompt": "SUI"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Sender",
+           "prompt": "0x6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e21",
+           "paginate": true
+         },
+         {
+           "header": "WARNING",
+           "prompt": "Sender is not the signing account"
+         },
+         {
+           "header": "To",
+           "prompt": "0x4f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b",
+           "paginate": true
+         },
+         {
+           "header": "Amount",
+           "prompt": "SUI 0.001"
+         },
+         {
+           "header": "Max Gas",
+           "prompt": "SUI 0.000001036"
+         },
+         {
+           "text": "Sign Transaction?",
+           "x": 19,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+    ]);
+  });
 
   it("can blind sign an unknown transaction", async function () {
     const path = "44'/784'/0'";
@@ -85,6 +1330,15 @@ describe("Signing tests", function() {
            "header": "WARNING",
            "prompt": "Transaction not recognized"
          },
+         {
+           "header": "Unsupported",
+           "prompt": "Transaction kind 5"
+         },
+         {
+           "header": "Transaction Digest",
+           "prompt": "CUML15n44VHsh7kFptCS1TTPrkRCnNa5GDtaEcd1qiVH",
+           "paginate": true
+         },
          {
            "header": "Transaction Hash",
            "prompt": "0xfc2bce70e1cb980a6d49a32ff770a782ee13dabdecee085b82e0fdad5e92fcdd"
@@ -109,6 +1363,62 @@ describe("Signing tests", function() {
     await toggleBlindSigningSettings();
   });
 
+  it("can blind sign once, after which blind signing is disabled", async function () {
+    const path = "44'/784'/0'";
+    const txn = Buffer.from("00000000050205546e7f126d2f40331a543b9608439b582fd0d103000000000000002080fdabcc90498e7eb8413b140c4334871eeafa5a86203fd9cfdb032f604f49e1284af431cf032b5d85324135bf9a3073e920d7f5020000000000000020a06f410c175e828c24cee84cb3bd95cff25c33fbbdcb62c6596e8e423784ffe702d08074075c7097f361e8b443e2075a852a2292e8a08074075c7097f361e8b443e2075a852a2292e80180969800000000001643fb2578ff7191c643079a62c1cca8ec2752bc05546e7f126d2f40331a543b9608439b582fd0d103000000000000002080fdabcc90498e7eb8413b140c4334871eeafa5a86203fd9cfdb032f604f49e101000000000000002c01000000000000", "hex");
+    const prompts =
+       [
+         {
+           "header": "WARNING",
+           "prompt": "Transaction not recognized"
+         },
+         {
+           "header": "Unsupported",
+           "prompt": "Transaction kind 5"
+         },
+         {
+           "header": "Transaction Digest",
+           "prompt": "CUML15n44VHsh7kFptCS1TTPrkRCnNa5GDtaEcd1qiVH",
+           "paginate": true
+         },
+         {
+           "header": "Transaction Hash",
+           "prompt": "0xfc2bce70e1cb980a6d49a32ff770a782ee13dabdecee085b82e0fdad5e92fcdd"
+         },
+         {
+           "text": "Blind Sign Transaction?",
+           "x": 4,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ];
+
+    await toggleBlindSignOnceSettings();
+    await Axios.delete(BASE_URL + "/events");
+    await testTransaction(path, txn, prompts)();
+    await Axios.delete(BASE_URL + "/events");
+    await sendCommandExpectFail(async (client : Sui) => {
+      await client.signTransaction(path, txn);
+    });
+  });
+
+  it("should reject a transaction from another sender, if sender mismatches are rejected", async function () {
+    const path = "44'/784'/0'";
+    const txn = Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b0202000101000001010200000101006fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e210112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020f2fd3c87b227f1015182fe4348ed680d7ed32bcd3269704252c03e1d0b13d30d6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e2101000000000000000c0400000000000000", "hex");
+
+    await toggleSenderMismatchSettings();
+    await Axios.delete(BASE_URL + "/events");
+    await sendCommandExpectFail(async (client : Sui) => {
+      await client.signTransaction(path, txn);
+    }, 0x6a84);
+    // reset back to warning
+    await toggleSenderMismatchSettings();
+  });
+
   it("should reject signing a non-SUI coin transaction, if blind signing is not enabled", async function () {
     const path = "44'/784'/0'";
     const txn = Buffer.from("AAAAAAADAQAe2uv1Mds+xCVK5Jv/Dv5cgEl/9DthDcpbjWcsmFpzbs6BNQAAAAAAIKPD8GQqgBpJZRV+nFDRE7rqR0Za8x0pyfLusVdpPPVRAAgADl+jHAAAAAAg5y3MHATlk+Ik5cPIdEz5iPANs1jcXZHVGjh4Mb16lwkCAgEAAAEBAQABAQIAAAECAF/sd27xyQe/W+gY4WRtPlQro1siWQu79s0pxbbCSRafAfnjaU5yJSFFDJznsAaBqbkiR9CB8DJqWki8fn8AUZeQz4E1AAAAAAAgTRU/MsawTJirpVwjDF8gyiEbaT0+7J0V8ifUEGGBkcVf7Hdu8ckHv1voGOFkbT5UK6NbIlkLu/bNKcW2wkkWn+gDAAAAAAAA8NdGAAAAAAAA", "base64");
@@ -118,13 +1428,22 @@ describe("Signing tests", function() {
     });
   });
 
+  it("should reject a transfer that does not account for all the split SUI, if blind signing is not enabled", async function () {
+    const path = "44'/784'/0'";
+    const txn = Buffer.from("000000000003000840420f0000000000000880841e000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b020200020100000101000101030000000001020056b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba50112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020222222222222222222222222222222222222222222222222222222222222222256b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5e80300000000000080841e000000000000", "hex");
+
+    await sendCommandExpectFail(async (client : Sui) => {
+      await client.signTransaction(path, txn);
+    }, 0x6a9a);
+  });
+
   it("should reject signing an unknown transaction, if blind signing is not enabled", async function () {
     const path = "44'/784'/0'";
     const txn = Buffer.from("00000000050205546e7f126d2f40331a543b9608439b582fd0d103000000000000002080fdabcc90498e7eb8413b140c4334871eeafa5a86203fd9cfdb032f604f49e1284af431cf032b5d85324135bf9a3073e920d7f5020000000000000020a06f410c175e828c24cee84cb3bd95cff25c33fbbdcb62c6596e8e423784ffe702d08074075c7097f361e8b443e2075a852a2292e8a08074075c7097f361e8b443e2075a852a2292e80180969800000000001643fb2578ff7191c643079a62c1cca8ec2752bc05546e7f126d2f40331a543b9608439b582fd0d103000000000000002080fdabcc90498e7eb8413b140c4334871eeafa5a86203fd9cfdb032f604f49e101000000000000002c01000000000000", "hex");
 
     await sendCommandExpectFail(async (client : Sui) => {
       await client.signTransaction(path, txn);
-    });
+    }, 0x6a91);
   });
 
   it("Rejects a blind sign with mismatching lengths", async function () {
@@ -148,9 +1467,138 @@ describe("Signing tests", function() {
         const { publicKey } = await client.getPublicKey(path);
         expect(publicKey.length>0).to.equal(true);
       },
-      []);
+      pathPrompts(path));
+    await Axios.delete(BASE_URL + "/events");
+    // reset back to disabled
+    await toggleBlindSigningSettings();
+  });
+
+  it("should reject a transaction with a non-transaction intent, even if blind signing is enabled", async function () {
+    const path = "44'/784'/0'";
+    const txn = Buffer.from("000000000002000840420f000000000000204f2370b2a4810ad6c8e1cfd92cc8c8818fef8f59e3a80cea17871f78d850ba4b0202000101000001010200000101006fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e210112a6d0c44edc630d2724b1f57fea4f93308b1d22164402c65778bd99379c4733070000000000000020f2fd3c87b227f1015182fe4348ed680d7ed32bcd3269704252c03e1d0b13d30d6fb21feead027da4873295affd6c4f3618fe176fa2fbf3e7b5ef1d9463b31e2101000000000000000c0400000000000000", "hex");
+
+    await toggleBlindSigningSettings();
+    await Axios.delete(BASE_URL + "/events");
+    // Personal message scope
+    await sendCommandExpectFail(async (client : Sui) => {
+      await client.signTransaction(path, Buffer.concat([Buffer.from([3, 0, 0]), txn.subarray(3)]));
+    });
+    // Narwhal app id
+    await sendCommandExpectFail(async (client : Sui) => {
+      await client.signTransaction(path, Buffer.concat([Buffer.from([0, 0, 1]), txn.subarray(3)]));
+    });
     await Axios.delete(BASE_URL + "/events");
     // reset back to disabled
     await toggleBlindSigningSettings();
   });
 });
+
+function testPersonalMessage(path: string, message: Buffer, prompts: any[]) {
+  return async () => {
+    await sendCommandAndAccept(async (client : Sui) => {
+
+      const { publicKey } = await client.getPublicKey(path);
+
+      // We don't want the prompts from getPublicKey in our result
+      await Axios.delete(BASE_URL + "/events");
+
+      // The personal message intent, then the message as a BCS vector<u8>
+      const intentMessage = Buffer.concat([Buffer.from([3, 0, 0, message.length]), message]);
+      const sig = await client.signPersonalMessage(path, intentMessage);
+      expect(sig.signature.length).to.equal(64);
+      const pass = nacl.crypto_sign_verify_detached(
+          sig.signature,
+          blake2b(32).update(intentMessage).digest(),
+          publicKey,
+      );
+      expect(pass).to.equal(true);
+    }, [...pathPrompts(path), ...prompts]);
+  }
+}
+
+describe("Personal message signing tests", function() {
+  before( async function() {
+    while(!nacl) await new Promise(r => setTimeout(r, 100));
+  })
+
+  it("can sign a text message",
+     testPersonalMessage(
+       "44'/784'/0'",
+       Buffer.from("Hello, Sui!", "utf8"),
+       [
+         {
+           "header": "Sign",
+           "prompt": "Personal Message"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Message",
+           "prompt": "Hello, Sui!"
+         },
+         {
+           "text": "Sign Message?",
+           "x": 26,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("can sign a binary message",
+     testPersonalMessage(
+       "44'/784'/0'",
+       Buffer.from("00ff10ab", "hex"),
+       [
+         {
+           "header": "Sign",
+           "prompt": "Personal Message"
+         },
+         {
+           "header": "From",
+           "prompt": "0x56b19e720f3bfa8caaef806afdd5dfaffd0d6ec9476323a14d1638ad734b2ba5",
+           "paginate": true
+         },
+         {
+           "header": "Path",
+           "prompt": "m/44'/784'/0'",
+           "paginate": true
+         },
+         {
+           "header": "Message",
+           "prompt": "0x00ff10ab"
+         },
+         {
+           "text": "Sign Message?",
+           "x": 26,
+           "y": 11
+         },
+         {
+           "text": "Confirm",
+           "x": 43,
+           "y": 11,
+         }
+       ]
+     ));
+
+  it("should reject a personal message with a transaction intent", async function () {
+    const path = "44'/784'/0'";
+    const message = Buffer.from("0000000b48656c6c6f2c2053756921", "hex");
+
+    await sendCommandExpectFail(async (client : Sui) => {
+      await client.signPersonalMessage(path, message);
+    });
+  });
+});